@@ -0,0 +1,323 @@
+//! The `noirdoc.toml` configuration.
+
+use crate::error::Error;
+use crate::filter::{self, SourceFilter};
+use crate::ordering::OrderStrategy;
+use crate::parser::Span;
+use crate::render::docusaurus::{SidebarFormat, SidebarMode};
+use crate::render::OutputFormat;
+use globset::Glob;
+use serde::Deserialize;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// File name of the configuration looked up in the input directory.
+pub const CONFIG_FILE_NAME: &str = "noirdoc.toml";
+
+const DEFAULT_DOCUSAURUS_BASE_PATH: &str = "/docs";
+const DEFAULT_SIDEBAR_FILE: &str = "sidebars.js";
+const DEFAULT_MODULE_SIDEBAR_FILE: &str = "sidebars-api.js";
+
+/// Settings read from `noirdoc.toml`; every table and key is optional.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The `[site]` table.
+    pub site: SiteConfig,
+    /// The `[output]` table.
+    pub output: OutputConfig,
+    /// The `[source]` table.
+    pub source: SourceConfig,
+    /// The `[features]` table.
+    pub features: FeaturesConfig,
+    /// The `[[extern]]` tables, one per externally documented crate.
+    #[serde(rename = "extern")]
+    pub externs: Vec<ExternConfig>,
+}
+
+/// Titles and URLs of the generated site.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SiteConfig {
+    /// Title of the overview page.
+    pub title: String,
+    /// Text shown on the overview page above the list of crates.
+    pub intro: String,
+    /// Doc ID of the overview page.
+    pub overview_id: String,
+    /// Sidebar label of the overview page.
+    pub overview_label: String,
+    /// Route the generated docs are served under, e.g. `/reference/aztec-nr`. For Docusaurus it
+    /// must match the docs plugin's `routeBasePath` and defaults to its `/docs`; other formats
+    /// default to `/`.
+    pub base_path: Option<String>,
+    /// Browsable URL of the input directory, e.g. `https://github.com/org/repo/blob/master/aztec-nr`.
+    /// Crates read from a model keep the URL recorded in it instead.
+    pub repository_url: Option<String>,
+}
+
+/// Where and in which format the documentation is written.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputConfig {
+    /// The output backend.
+    pub format: OutputFormat,
+    /// Output directory; relative to the config file when read from one.
+    pub dir: PathBuf,
+    /// Directory of the pages, relative to `dir`.
+    pub docs_dir: PathBuf,
+    /// Docusaurus sidebars file, relative to `dir`. Defaults to `sidebars.js`, or to
+    /// `sidebars-api.js` in `module` mode so the site's own `sidebars.js` is left alone.
+    pub sidebar_file: Option<PathBuf>,
+    /// Key of the generated sidebar in the sidebars file.
+    pub sidebar_id: String,
+    /// `module` puts all items of a module on its page, `item` gives every struct, trait, function
+    /// and constant a page of its own and turns module pages into summary tables.
+    pub page_mode: PageMode,
+    /// `js` or `ts` write `sidebar_file` with a matching extension, `autogenerated` writes
+    /// `_category_.json` files for Docusaurus' autogenerated sidebars instead.
+    pub sidebar_format: SidebarFormat,
+    /// `replace` writes all of `sidebar_file`, `module` writes only the API items for the site's own
+    /// sidebars to import, and `patch` swaps the items of `sidebar_id` (or of its `sidebar_category`
+    /// category) in an existing `sidebar_file`, leaving the rest of the file as it is.
+    pub sidebar_mode: SidebarMode,
+    /// Label of the category whose items `patch` mode replaces.
+    pub sidebar_category: Option<String>,
+    /// Whether sidebar categories can be collapsed at all.
+    pub sidebar_collapsible: bool,
+    /// Categories at least this deep start collapsed: 0 collapses crates, 1 their modules, and so on.
+    pub sidebar_collapsed_depth: usize,
+}
+
+/// How items are split into pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PageMode {
+    /// One page per module.
+    #[default]
+    Module,
+    /// One page per module and one per item.
+    Item,
+}
+
+/// Which source files are documented, and in which order.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SourceConfig {
+    /// Globs of files to document, relative to the input directory.
+    pub include: Vec<String>,
+    /// Globs of files and directories to leave out.
+    pub exclude: Vec<String>,
+    /// Order of files and items.
+    pub order: OrderStrategy,
+}
+
+/// Optional parts of the output.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeaturesConfig {
+    /// Write an item inventory per crate for other runs to link to.
+    pub inventory: bool,
+    /// Link each page to its source file when its crate's repository URL is known.
+    pub source_links: bool,
+    /// Report documentation lint warnings.
+    pub lints: bool,
+}
+
+/// A crate documented elsewhere, linked through the inventory its documentation run wrote.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExternConfig {
+    /// URL the other documentation is served at.
+    pub base_url: String,
+    /// Path of its inventory JSON file.
+    pub inventory: PathBuf,
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        SiteConfig {
+            title: "Aztec.nr Project".to_string(),
+            intro: "Welcome to the Aztec.nr project documentation. This project consists of the following libraries:".to_string(),
+            overview_id: "aztec-nr".to_string(),
+            overview_label: "Aztec.nr Overview".to_string(),
+            base_path: None,
+            repository_url: None,
+        }
+    }
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        OutputConfig {
+            format: OutputFormat::default(),
+            dir: PathBuf::from("docusaurus_output"),
+            docs_dir: PathBuf::from("docs"),
+            sidebar_file: None,
+            sidebar_id: "someSidebar".to_string(),
+            page_mode: PageMode::default(),
+            sidebar_format: SidebarFormat::default(),
+            sidebar_mode: SidebarMode::default(),
+            sidebar_category: None,
+            sidebar_collapsible: true,
+            sidebar_collapsed_depth: 1,
+        }
+    }
+}
+
+impl Default for SourceConfig {
+    fn default() -> Self {
+        SourceConfig {
+            include: filter::DEFAULT_INCLUDE.iter().map(|s| s.to_string()).collect(),
+            exclude: filter::DEFAULT_EXCLUDE.iter().map(|s| s.to_string()).collect(),
+            order: OrderStrategy::default(),
+        }
+    }
+}
+
+impl Default for FeaturesConfig {
+    fn default() -> Self {
+        FeaturesConfig {
+            inventory: true,
+            source_links: true,
+            lints: true,
+        }
+    }
+}
+
+impl Config {
+    /// Reads a config file, resolving its relative paths against the file's directory.
+    pub fn load(path: &Path) -> Result<Config, Error> {
+        let content = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+        let mut config: Config = toml::from_str(&content).map_err(|e| Error::Config {
+            path: Some(path.to_path_buf()),
+            key: e.span().and_then(|range| key_at(&content, range.start)),
+            span: e.span().map(|range| span_at(&content, range)),
+            message: e.message().to_string(),
+        })?;
+
+        // Relative paths in the config file are relative to the file itself
+        let config_dir = path.parent().unwrap_or(Path::new(""));
+        config.output.dir = config_dir.join(&config.output.dir);
+        for external in &mut config.externs {
+            external.inventory = config_dir.join(&external.inventory);
+        }
+
+        config.validate().map_err(|e| e.in_file(path))?;
+        Ok(config)
+    }
+
+    /// Checks settings that the file format alone cannot. Errors name the offending key, e.g.
+    /// "invalid value for `output.sidebar_id`: ...".
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |key: &str, message: &str| Err(Error::config(key, message));
+
+        if self.site.overview_id.trim().is_empty() {
+            return invalid("site.overview_id", "must not be empty");
+        }
+        if self.site.base_path.as_ref().is_some_and(|base_path| !base_path.starts_with('/')) {
+            return invalid("site.base_path", "must start with '/'");
+        }
+        if let Some(url) = &self.site.repository_url {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return invalid("site.repository_url", "must be an http(s) URL");
+            }
+        }
+
+        // Sidebars are written as JSON, so any key can be quoted
+        if self.output.sidebar_id.trim().is_empty() {
+            return invalid("output.sidebar_id", "must not be empty");
+        }
+        if self.output.sidebar_mode != SidebarMode::Replace && self.output.sidebar_format == SidebarFormat::Autogenerated {
+            return invalid("output.sidebar_mode", "requires `output.sidebar_format` to be 'js' or 'ts'");
+        }
+        if self.output.sidebar_category.is_some() && self.output.sidebar_mode != SidebarMode::Patch {
+            return invalid("output.sidebar_category", "only applies when `output.sidebar_mode` is 'patch'");
+        }
+        if self.output.docs_dir.is_absolute() {
+            return invalid("output.docs_dir", "must be relative to `output.dir`");
+        }
+        if self.output.sidebar_file.as_ref().is_some_and(|sidebar_file| sidebar_file.is_absolute()) {
+            return invalid("output.sidebar_file", "must be relative to `output.dir`");
+        }
+
+        if self.source.include.is_empty() {
+            return invalid("source.include", "must contain at least one glob");
+        }
+        for (key, globs) in [("source.include", &self.source.include), ("source.exclude", &self.source.exclude)] {
+            for (index, glob) in globs.iter().enumerate() {
+                if let Err(e) = Glob::new(glob) {
+                    return invalid(&format!("{}[{}]", key, index), &e.to_string());
+                }
+            }
+        }
+
+        for (index, external) in self.externs.iter().enumerate() {
+            if external.base_url.trim().is_empty() {
+                return invalid(&format!("extern[{}].base_url", index), "must not be empty");
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn source_filter(&self) -> Result<SourceFilter, Error> {
+        SourceFilter::new(&self.source.include, &self.source.exclude)
+    }
+
+    // Docusaurus serves docs under `/docs` unless its `routeBasePath` says otherwise
+    pub(crate) fn base_path(&self) -> &str {
+        match (&self.site.base_path, self.output.format) {
+            (Some(base_path), _) => base_path,
+            (None, OutputFormat::Docusaurus) => DEFAULT_DOCUSAURUS_BASE_PATH,
+            (None, _) => "/",
+        }
+    }
+
+    // `sidebars.js` becomes `sidebars.ts` for TypeScript sidebars
+    pub(crate) fn sidebar_file(&self) -> PathBuf {
+        let sidebar_file = match (&self.output.sidebar_file, self.output.sidebar_mode) {
+            (Some(sidebar_file), _) => sidebar_file.clone(),
+            (None, SidebarMode::Module) => PathBuf::from(DEFAULT_MODULE_SIDEBAR_FILE),
+            (None, _) => PathBuf::from(DEFAULT_SIDEBAR_FILE),
+        };
+        match self.output.sidebar_format {
+            SidebarFormat::Ts => sidebar_file.with_extension("ts"),
+            _ => sidebar_file,
+        }
+    }
+
+    pub(crate) fn sidebar_path(&self) -> PathBuf {
+        self.output.dir.join(self.sidebar_file())
+    }
+}
+
+// Dotted name of the setting on the line containing `offset`, e.g. `output.sidebar_mode`, from
+// its key and the table header above it
+fn key_at(content: &str, offset: usize) -> Option<String> {
+    let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = content[line_start..].lines().next().unwrap_or("").trim();
+    let table_name = |line: &str| line.trim_matches(|c| c == '[' || c == ']').trim().to_string();
+    if line.starts_with('[') {
+        return Some(table_name(line));
+    }
+
+    let key = line.split_once('=')?.0.trim().trim_matches('"');
+    let table = content[..line_start].lines().rev().map(str::trim).find(|line| line.starts_with('['));
+    Some(match table {
+        Some(table) => format!("{}.{}", table_name(table), key),
+        None => key.to_string(),
+    })
+}
+
+// 1-based line and column of a byte range in the config file
+fn span_at(content: &str, range: Range<usize>) -> Span {
+    let before = &content[..range.start];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Span {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+        length: content[range].chars().count().max(1),
+    }
+}
@@ -0,0 +1,259 @@
+//! Errors and lint warnings, rendered for people or as JSON or SARIF.
+
+use crate::error::Error;
+use crate::parser::Span;
+use serde::Serialize;
+use serde_json::json;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+/// How serious a diagnostic is.
+pub enum Severity {
+    /// Something was left out of the output, or nothing was written.
+    Error,
+    /// A documentation problem, such as a missing doc comment.
+    Warning,
+}
+
+/// A problem found while generating documentation, rendered compiler-style with a source snippet.
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    /// Whether the problem is an error or a warning.
+    pub severity: Severity,
+    /// Stable identifier of the kind of problem, e.g. `parse-error` or `missing-docs`.
+    pub code: &'static str,
+    /// What went wrong.
+    pub message: String,
+    /// The file the problem is in, when it is in one.
+    pub path: Option<PathBuf>,
+    /// Where in `path` the problem is.
+    pub span: Option<Span>,
+    /// A suggested fix.
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub(crate) fn warning(code: &'static str, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            code,
+            message: message.into(),
+            path: None,
+            span: None,
+            help: None,
+        }
+    }
+
+    pub(crate) fn error(code: &'static str, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            ..Diagnostic::warning(code, message)
+        }
+    }
+
+    pub(crate) fn at(mut self, path: impl Into<PathBuf>, span: Span) -> Self {
+        self.path = Some(path.into());
+        self.span = Some(span);
+        self
+    }
+
+    pub(crate) fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    // error[parse-error]: expected `)`
+    //   --> src/lib.nr:3:9
+    //    |
+    //  3 | fn oops( {
+    //    |          ^
+    //    = help: ...
+    /// Renders the diagnostic like a compiler error, quoting the source line it points at.
+    pub fn render(&self) -> String {
+        let mut content = format!("{}[{}]: {}\n", self.severity, self.code, self.message);
+
+        let source_line = match (&self.path, self.span) {
+            (Some(path), Some(span)) => fs::read_to_string(path)
+                .ok()
+                .and_then(|source| source.lines().nth(span.line.saturating_sub(1)).map(|line| line.to_string())),
+            _ => None,
+        };
+        let gutter = match (self.span, &source_line) {
+            (Some(span), Some(_)) => span.line.to_string().len(),
+            _ => 1,
+        };
+        let padding = " ".repeat(gutter);
+
+        match (&self.path, self.span) {
+            (Some(path), Some(span)) => content.push_str(&format!("{}--> {}:{}:{}\n", padding, path.display(), span.line, span.column)),
+            (Some(path), None) => content.push_str(&format!("{}--> {}\n", padding, path.display())),
+            _ => {}
+        }
+
+        if let (Some(span), Some(line)) = (self.span, &source_line) {
+            // Tabs would throw off the underline, so render them as single spaces
+            let line = line.replace('\t', " ");
+            let underline = format!("{}{}", " ".repeat(span.column.saturating_sub(1)), "^".repeat(span.length.max(1)));
+            content.push_str(&format!("{} |\n", padding));
+            content.push_str(&format!("{} | {}\n", span.line, line));
+            content.push_str(&format!("{} | {}\n", padding, underline));
+        }
+
+        if let Some(help) = &self.help {
+            content.push_str(&format!("{} = help: {}\n", padding, help));
+        }
+
+        content
+    }
+}
+
+/// Output format of diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageFormat {
+    /// Compiler-style text with source snippets.
+    #[default]
+    Human,
+    /// One JSON object per diagnostic per line.
+    Json,
+    /// A single SARIF 2.1.0 log, for code scanning annotations.
+    Sarif,
+}
+
+impl FromStr for MessageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            "sarif" => Ok(MessageFormat::Sarif),
+            _ => Err(format!("unknown message format '{}', expected 'human', 'json' or 'sarif'", s)),
+        }
+    }
+}
+
+/// Renders all diagnostics in one of the message formats.
+pub fn format_diagnostics(diagnostics: &[Diagnostic], format: MessageFormat) -> String {
+    match format {
+        MessageFormat::Human => diagnostics.iter().map(|diagnostic| diagnostic.render()).collect::<Vec<_>>().join("\n"),
+        MessageFormat::Json => diagnostics
+            .iter()
+            .map(|diagnostic| format!("{}\n", serde_json::to_string(diagnostic).unwrap_or_default()))
+            .collect(),
+        MessageFormat::Sarif => format!("{}\n", serde_json::to_string_pretty(&to_sarif(diagnostics)).unwrap_or_default()),
+    }
+}
+
+fn to_sarif(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let mut rules: Vec<&str> = diagnostics.iter().map(|diagnostic| diagnostic.code).collect();
+    rules.sort();
+    rules.dedup();
+
+    let results: Vec<serde_json::Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut text = diagnostic.message.clone();
+            if let Some(help) = &diagnostic.help {
+                text.push_str(&format!("\nhelp: {}", help));
+            }
+
+            let mut result = json!({
+                "ruleId": diagnostic.code,
+                "level": diagnostic.severity,
+                "message": { "text": text },
+            });
+            if let Some(path) = &diagnostic.path {
+                let mut location = json!({ "artifactLocation": { "uri": artifact_uri(path) } });
+                if let Some(span) = diagnostic.span {
+                    location["region"] = json!({
+                        "startLine": span.line,
+                        "startColumn": span.column,
+                        "endColumn": span.column + span.length.max(1),
+                    });
+                }
+                result["locations"] = json!([{ "physicalLocation": location }]);
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "noirdoc",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<_>>(),
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+// Relative paths stay relative so code scanning can match them to the checkout
+fn artifact_uri(path: &Path) -> String {
+    let parts: Vec<String> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            Component::ParentDir => Some("..".to_string()),
+            _ => None,
+        })
+        .collect();
+    if path.is_absolute() {
+        format!("file:///{}", parts.join("/"))
+    } else {
+        parts.join("/")
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::Io { path, source } => Diagnostic {
+                path: Some(path.clone()),
+                ..Diagnostic::error("io-error", source.to_string())
+            },
+            Error::Parse { path, message, span } => {
+                let diagnostic = Diagnostic::error("parse-error", message.clone()).at(path.clone(), *span);
+                // Only Noir sources can be excluded or skipped, not documentation models
+                if path.extension().is_some_and(|extension| extension == "nr") {
+                    diagnostic.with_help("exclude the file with `source.exclude` or add a `// noirdoc: skip` line to leave it out of the docs")
+                } else {
+                    diagnostic
+                }
+            }
+            Error::Config { path, key, span, message } => Diagnostic {
+                path: path.clone(),
+                span: *span,
+                ..Diagnostic::error(
+                    "config-error",
+                    match key {
+                        Some(key) => format!("invalid value for `{}`: {}", key, message),
+                        None => message.clone(),
+                    },
+                )
+            },
+            Error::Resolution { path, message } => Diagnostic {
+                path: path.clone(),
+                ..Diagnostic::error("resolution-error", message.clone())
+            },
+        }
+    }
+}
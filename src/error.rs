@@ -0,0 +1,104 @@
+use crate::parser::Span;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Everything that can go wrong while generating documentation.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading sources or writing output failed.
+    Io {
+        /// The file or directory being read or written.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+    /// A source file could not be parsed.
+    Parse {
+        /// The source file.
+        path: PathBuf,
+        /// What the parser expected.
+        message: String,
+        /// Where parsing stopped.
+        span: Span,
+    },
+    /// The configuration is invalid.
+    Config {
+        /// The config file, when the setting came from one.
+        path: Option<PathBuf>,
+        /// The offending setting, e.g. `output.sidebar_id`, when known.
+        key: Option<String>,
+        /// Its place in the config file, when known.
+        span: Option<Span>,
+        /// What is wrong with it.
+        message: String,
+    },
+    /// A link target or external inventory could not be resolved.
+    Resolution {
+        /// The file that could not be resolved, when there is one.
+        path: Option<PathBuf>,
+        /// What could not be resolved.
+        message: String,
+    },
+}
+
+impl Error {
+    /// An I/O error on `path`.
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Error::Io { path: path.into(), source }
+    }
+
+    pub(crate) fn config(key: &str, message: impl Into<String>) -> Self {
+        Error::Config {
+            path: None,
+            key: Some(key.to_string()),
+            span: None,
+            message: message.into(),
+        }
+    }
+
+    // Attach the file a configuration error came from
+    pub(crate) fn in_file(self, file: &std::path::Path) -> Self {
+        match self {
+            Error::Config { key, span, message, .. } => Error::Config {
+                path: Some(file.to_path_buf()),
+                key,
+                span,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::Parse { path, message, span } => write!(f, "{}:{}:{}: failed to parse: {}", path.display(), span.line, span.column, message),
+            Error::Config { path, key, span, message } => {
+                match (path, span) {
+                    (Some(path), Some(span)) => write!(f, "{}:{}:{}: ", path.display(), span.line, span.column)?,
+                    (Some(path), None) => write!(f, "{}: ", path.display())?,
+                    _ => {}
+                }
+                match key {
+                    Some(key) => write!(f, "invalid value for `{}`: {}", key, message),
+                    None => write!(f, "{}", message),
+                }
+            }
+            Error::Resolution { path, message } => match path {
+                Some(path) => write!(f, "{}: {}", path.display(), message),
+                None => write!(f, "{}", message),
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
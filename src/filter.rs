@@ -0,0 +1,58 @@
+use crate::error::Error;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+pub(crate) const SKIP_MARKER: &str = "// noirdoc: skip";
+pub(crate) const DEFAULT_INCLUDE: &[&str] = &["**/*.nr"];
+pub(crate) const DEFAULT_EXCLUDE: &[&str] = &["**/target/**"];
+
+pub(crate) struct SourceFilter {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl SourceFilter {
+    pub(crate) fn new(include: &[String], exclude: &[String]) -> Result<Self, Error> {
+        Ok(SourceFilter {
+            include: build_glob_set(include)?,
+            exclude: build_glob_set(exclude)?,
+        })
+    }
+
+    // Globs are matched against the path relative to the input directory
+    pub(crate) fn is_included(&self, relative_path: &Path) -> bool {
+        self.include.is_match(relative_path) && !self.exclude.is_match(relative_path)
+    }
+
+    pub(crate) fn is_excluded_dir(&self, relative_path: &Path) -> bool {
+        self.exclude.is_match(relative_path) || self.exclude.is_match(relative_path.join("_"))
+    }
+}
+
+impl Default for SourceFilter {
+    fn default() -> Self {
+        let include: Vec<String> = DEFAULT_INCLUDE.iter().map(|s| s.to_string()).collect();
+        let exclude: Vec<String> = DEFAULT_EXCLUDE.iter().map(|s| s.to_string()).collect();
+        SourceFilter::new(&include, &exclude).expect("default globs are valid")
+    }
+}
+
+// A file opts out of documentation with a `// noirdoc: skip` line
+pub(crate) fn has_skip_marker(content: &str) -> bool {
+    content.lines().any(|line| line.trim() == SKIP_MARKER)
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, Error> {
+    let invalid = |e: globset::Error| Error::Config {
+        path: None,
+        key: None,
+        span: None,
+        message: e.to_string(),
+    };
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).map_err(invalid)?);
+    }
+    builder.build().map_err(invalid)
+}
@@ -1,33 +1,39 @@
-use crate::parser::NoirFile;
+use crate::config::{Config, PageMode};
+use crate::error::Error;
+use crate::filter::{self, SourceFilter};
+use crate::inventory::{self, Inventory, LinkResolver};
+use crate::ordering::{self, OrderStrategy};
+use crate::parser::{NoirConstant, NoirFile, NoirFunction, NoirImpl, NoirStruct, NoirTrait, NoirTypeAlias};
+use crate::render::docusaurus::SidebarFormat;
+use crate::render::OutputFormat;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
 use regex::Regex;
 
-
-pub struct DocusaurusDoc {
-    pub content: String,
-    pub path: PathBuf,
-}
-
-pub enum SidebarItem {
-    Category { label: String, items: Vec<SidebarItem> },
-    Doc { id: String, label: String },
+/// A parsed crate.
+#[derive(Debug)]
+pub struct Library {
+    /// The crate's name, from `Nargo.toml` or its directory.
+    pub name: String,
+    /// Input directory the crate was found in; file paths are relative to it.
+    pub root: PathBuf,
+    /// Browsable URL of `root` in the crate's repository, for links to source files.
+    pub repository_url: Option<String>,
+    /// One file per module.
+    pub files: Vec<NoirFile>,
 }
 
-struct Library {
-    name: String,
-    files: Vec<NoirFile>,
-}
+// `@param name description`
+static PARAM_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"@param\s+(\w+)\s+(.+)").unwrap());
 
-fn parse_doc_comment(doc_comment: &str) -> (String, Vec<(String, String, String)>) {
+pub(crate) fn parse_doc_comment(doc_comment: &str) -> (String, Vec<(String, String, String)>) {
     let mut description = String::new();
     let mut params = Vec::new();
 
-    let param_regex = Regex::new(r"@param\s+(\w+)\s+(.+)").unwrap();
-
     for line in doc_comment.lines() {
-        if let Some(captures) = param_regex.captures(line) {
+        if let Some(captures) = PARAM_REGEX.captures(line) {
             let param_name = captures.get(1).unwrap().as_str().to_string();
             let param_description = captures.get(2).unwrap().as_str().to_string();
             params.push((param_name, String::new(), param_description));
@@ -40,294 +46,763 @@ fn parse_doc_comment(doc_comment: &str) -> (String, Vec<(String, String, String)
     (description.trim().to_string(), params)
 }
 
-pub fn generate_docusaurus_docs(input_dir: &str) -> (Vec<DocusaurusDoc>, Vec<SidebarItem>) {
-    let mut docs = Vec::new();
-    let mut libraries = HashMap::new();
-
-    // Parse all Noir files
-    for entry in fs::read_dir(input_dir).unwrap() {
-        let entry = entry.unwrap();
-        let path = entry.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("nr") {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if content.contains("// typedoc: true") {
-                    let file_name = path.file_stem().unwrap().to_str().unwrap().to_string();
-                    let noir_file = crate::parser::parse_noir_file(path.to_str().unwrap()).unwrap();
-                    libraries.insert(file_name.clone(), Library { name: file_name, files: vec![noir_file] });
-                }
-            }
-        }
+// First sentence of the description, e.g. for summary tables and page descriptions
+pub(crate) fn doc_summary(doc_comment: &str) -> String {
+    let (description, _) = parse_doc_comment(doc_comment);
+    let paragraph = description.split("\n\n").next().unwrap_or("");
+    // Lines of `/** ... */` comments usually start with `*`
+    let paragraph = paragraph.lines().map(|line| line.trim().trim_start_matches('*').trim()).collect::<Vec<_>>().join(" ");
+    match sentence_end(&paragraph) {
+        Some(end) => paragraph[..=end].to_string(),
+        None => paragraph,
     }
+}
 
-    // Generate main overview page
-    docs.push(DocusaurusDoc {
-        content: generate_main_overview(&libraries),
-        path: PathBuf::from("aztec-nr.md"),
-    });
-
-    let mut sidebar = vec![SidebarItem::Doc {
-        id: "aztec-nr".to_string(),
-        label: "Aztec.nr Overview".to_string(),
-    }];
-
-    // Generate docs for each library (file in this case)
-    for (name, library) in libraries {
-        docs.push(DocusaurusDoc {
-            content: generate_library_doc(&library),
-            path: PathBuf::from(format!("{}.md", name)),
-        });
-        sidebar.push(SidebarItem::Doc {
-            id: name.clone(),
-            label: name,
-        });
-    }
-
-    (docs, sidebar)
-}
-fn parse_directory(dir: &Path, files: &mut Vec<NoirFile>) {
-    for entry in fs::read_dir(dir).unwrap() {
-        let entry = entry.unwrap();
-        let path = entry.path();
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("nr") {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if content.contains("// typedoc: true") {
-                    let noir_file = crate::parser::parse_noir_file(path.to_str().unwrap()).unwrap();
-                    files.push(noir_file);
-                }
-            }
-        } else if path.is_dir() {
-            parse_directory(&path, files);
-        }
+const ABBREVIATIONS: &[&str] = &["e.g.", "i.e.", "etc.", "vs.", "cf."];
+
+// Index of the `.` ending the first sentence: one followed by a space and an uppercase letter,
+// and not ending an abbreviation such as "e.g."
+fn sentence_end(text: &str) -> Option<usize> {
+    text.match_indices(". ").map(|(index, _)| index).find(|&index| {
+        let starts_sentence = text[index + 2..].chars().next().is_some_and(char::is_uppercase);
+        let word = text[..=index].rsplit(' ').next().unwrap_or("");
+        starts_sentence && !ABBREVIATIONS.contains(&word.to_lowercase().as_str())
+    })
+}
+
+// `unconstrained fn get<T, N>(storage: Map < T >, index: u32) -> T`
+pub(crate) fn function_signature(function: &NoirFunction) -> String {
+    let mut signature = String::new();
+    if function.is_unconstrained {
+        signature.push_str("unconstrained ");
+    }
+    signature.push_str(&format!("fn {}", function.name));
+    if !function.generic_params.is_empty() {
+        signature.push_str(&format!("<{}>", function.generic_params.join(", ")));
     }
+    let params: Vec<String> = function.receiver.iter().cloned().chain(function.params.iter().map(|param| format!("{}: {}", param.name, param.ty))).collect();
+    signature.push_str(&format!("({})", params.join(", ")));
+    if let Some(return_type) = &function.return_type {
+        signature.push_str(&format!(" -> {}", return_type));
+    }
+    signature
 }
 
-fn generate_main_overview(libraries: &HashMap<String, Library>) -> String {
-    let mut content = String::from("# Aztec.nr Project\n\n");
-    content.push_str("Welcome to the Aztec.nr project documentation. This project consists of the following libraries:\n\n");
-    
-    for name in libraries.keys() {
-        content.push_str(&format!("- [{}]({})\n", name, name));
+// `impl<Context> AccountActions < Context >` or `impl Serialize < 3 > for Note`
+pub(crate) fn impl_signature(impl_item: &NoirImpl) -> String {
+    let mut signature = String::from("impl");
+    if !impl_item.generic_params.is_empty() {
+        signature.push_str(&format!("<{}>", impl_item.generic_params.join(", ")));
+    }
+    if let Some(trait_name) = &impl_item.trait_name {
+        signature.push_str(&format!(" {} for", trait_name));
     }
+    signature.push_str(&format!(" {}", impl_item.target));
+    signature
+}
+
+// `const MAX_NOTES: u32 = 16`
+pub(crate) fn constant_signature(constant: &NoirConstant) -> String {
+    format!("const {}: {} = {}", constant.name, constant.ty, constant.value)
+}
+
+// `type Context<N> = PrivateContext < N >`
+pub(crate) fn type_alias_signature(type_alias: &NoirTypeAlias) -> String {
+    let mut signature = format!("type {}", type_alias.name);
+    if !type_alias.generic_params.is_empty() {
+        signature.push_str(&format!("<{}>", type_alias.generic_params.join(", ")));
+    }
+    signature.push_str(&format!(" = {}", type_alias.ty));
+    signature
+}
+
+// Parse the Noir sources under `input_dir` into one library per crate, left for the caller to
+// order once every input is merged. Files that fail to read or parse are reported in the returned errors and otherwise skipped.
+pub(crate) fn parse_libraries(input_dir: &Path, config: &Config, source_filter: &SourceFilter) -> (Vec<Library>, Vec<Error>) {
+    let mut libraries = Vec::new();
+    let mut errors = Vec::new();
+
+    // Parse all Noir files matched by the include/exclude globs, grouped by crate
+    let root_crate = CrateRoot {
+        name: read_crate_name(input_dir).unwrap_or_else(|| dir_name(input_dir)),
+        dir: input_dir.to_path_buf(),
+        src_dir: crate_src_dir(input_dir),
+    };
+    parse_directory(input_dir, input_dir, &root_crate, source_filter, &mut libraries, &mut errors);
+    for library in &mut libraries {
+        library.repository_url = config.site.repository_url.clone();
+    }
+
+    (libraries, errors)
+}
 
-    content
+// Files are ordered by module path whatever the strategy: the directory walk would put `utils/`
+// and its children before `utils.nr`, their parent
+pub(crate) fn order_libraries(libraries: &mut [Library], ordering: OrderStrategy) {
+    if ordering.sorts_by_name() {
+        libraries.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    for library in libraries {
+        library.files.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+        for file in &mut library.files {
+            ordering::sort_file_items(file, ordering);
+        }
+    }
 }
 
-fn generate_library_doc(library: &Library) -> String {
-    let mut content = String::from(&format!("# {} Library\n\n", library.name));
-    
-    if let Some(file) = library.files.first() {
-        content.push_str(&generate_file_content(file));
+// Assign every page a path-qualified doc ID, unique across all crates, and record every
+// documented item so other runs, and links within this one, can point to them. Other runs link
+// to the pages with `served_extension`, see `Renderer::served_extension`
+pub(crate) fn layout_site<'a>(libraries: &'a [Library], config: &Config, served_extension: Option<&str>) -> (Site<'a>, Vec<Inventory>) {
+    let mut used_ids = HashSet::new();
+    let overview_id = unique_doc_id(config.site.overview_id.clone(), &mut used_ids);
+    let mut library_pages: Vec<LibraryPages> = libraries
+        .iter()
+        .map(|library| {
+            let index_id = unique_doc_id(format!("{}/index", library.name), &mut used_ids);
+            let mut module_pages: Vec<(String, &NoirFile)> = library
+                .files
+                .iter()
+                .map(|file| (unique_doc_id(module_doc_id(library, file), &mut used_ids), file))
+                .collect();
+            let item_pages = match config.output.page_mode {
+                PageMode::Module => Vec::new(),
+                PageMode::Item => item_pages(&module_pages, config.source.order, &mut used_ids),
+            };
+            if config.output.format == OutputFormat::Docusaurus && config.output.sidebar_format == SidebarFormat::Autogenerated {
+                move_into_folders(&mut module_pages, &item_pages, &mut used_ids);
+            }
+            let mut pages = LibraryPages {
+                index_id,
+                library,
+                module_pages,
+                item_pages,
+                impl_docs: Vec::new(),
+                trait_impls: Vec::new(),
+                type_uses: Vec::new(),
+            };
+            pages.impl_docs = impl_docs(&pages);
+            pages
+        })
+        .collect();
+
+    let inventories: Vec<Inventory> = library_pages
+        .iter()
+        .map(|pages| {
+            let mut inventory = inventory::build_inventory(config.base_path(), served_extension, pages);
+            ordering::sort_inventory_items(&mut inventory.items, config.source.order);
+            inventory
+        })
+        .collect();
+
+    let resolver = LinkResolver::default().with_local(&inventories, "");
+    let trait_impls: Vec<TraitImpl> = library_pages.iter().flat_map(|pages| trait_impls(pages, &resolver)).collect();
+    let aliases: HashMap<String, Vec<String>> = library_pages.iter().flat_map(|pages| alias_targets(pages, &resolver)).collect();
+    let type_uses: Vec<TypeUse> = library_pages.iter().flat_map(|pages| type_uses(pages, &resolver, &aliases)).collect();
+    for pages in &mut library_pages {
+        let prefix = format!("{}::", pages.library.name);
+        let involves = |path: &Option<String>| path.as_ref().is_some_and(|path| path.starts_with(&prefix));
+        pages.trait_impls = trait_impls.iter().filter(|trait_impl| involves(&trait_impl.trait_path) || involves(&trait_impl.type_path)).cloned().collect();
+        pages.type_uses = type_uses.iter().filter(|type_use| type_use.type_path.starts_with(&prefix)).cloned().collect();
     }
 
-    content
+    let site = Site {
+        overview_id,
+        libraries: library_pages,
+    };
+    (site, inventories)
 }
 
-fn generate_aztec_docs(aztec_library: &Library) -> (Vec<DocusaurusDoc>, Vec<SidebarItem>) {
-    let mut docs = Vec::new();
-    let mut sidebar_items = Vec::new();
+/// The page layout shared by every output backend: one overview page, one index page per crate
+/// and one page per module, each identified by a doc ID such as `aztec/context/private_context`.
+#[derive(Debug)]
+pub struct Site<'a> {
+    /// Doc ID of the overview page.
+    pub overview_id: String,
+    /// The pages of each crate, in the configured order.
+    pub libraries: Vec<LibraryPages<'a>>,
+}
+
+/// The pages of one crate.
+#[derive(Debug)]
+pub struct LibraryPages<'a> {
+    /// Doc ID of the crate's index page.
+    pub index_id: String,
+    /// The crate.
+    pub library: &'a Library,
+    /// Doc ID and file of each module page.
+    pub module_pages: Vec<(String, &'a NoirFile)>,
+    /// With `output.page_mode = "item"`, a page per struct, trait, type alias, function and
+    /// constant, in module order.
+    pub item_pages: Vec<ItemPage<'a>>,
+    /// Every impl block of the crate, in module order.
+    pub impl_docs: Vec<ImplDoc<'a>>,
+    /// Trait impls and derives, from any crate, of this crate's traits or for its structs.
+    pub trait_impls: Vec<TraitImpl<'a>>,
+    /// Uses, from any crate, of this crate's structs and type aliases.
+    pub type_uses: Vec<TypeUse>,
+}
 
-    // Generate main Aztec library page
-    docs.push(DocusaurusDoc {
-        content: generate_aztec_overview(aztec_library),
-        path: PathBuf::from("aztec/index.md"),
-    });
-    sidebar_items.push(SidebarItem::Doc {
-        id: "aztec/index".to_string(),
-        label: "Aztec Overview".to_string(),
-    });
+/// Where an impl block is documented. Inherent impls are shown with the struct they belong to,
+/// wherever in the crate they are written; trait impls stay on their own module's page.
+#[derive(Debug)]
+pub struct ImplDoc<'a> {
+    /// The impl block.
+    pub impl_item: &'a NoirImpl,
+    /// Index in `module_pages` of the module the block is written in.
+    pub module: usize,
+    /// The struct the block implements, as its module's index and its name, when it is
+    /// documented in the crate.
+    pub owner: Option<(usize, &'a str)>,
+    /// Heading ID of the block, unique on the page it is shown on.
+    pub anchor: String,
+    /// Heading IDs of the block's methods.
+    pub method_anchors: Vec<String>,
+}
 
-    // Generate pages for each component
-    for file in &aztec_library.files {
-        let file_path = format!("aztec/{}.md", file.name);
-        docs.push(DocusaurusDoc {
-            content: generate_file_doc(file),
-            path: PathBuf::from(&file_path),
-        });
-        sidebar_items.push(SidebarItem::Doc {
-            id: file_path,
-            label: file.name.clone(),
-        });
+/// A trait implemented for a type, by an impl block or a derive.
+#[derive(Debug, Clone)]
+pub struct TraitImpl<'a> {
+    /// The trait as written, e.g. `Serialize < 3 >`.
+    pub trait_name: String,
+    /// The implementing type as written.
+    pub type_name: String,
+    /// Full path of the trait, when it is documented in this run.
+    pub trait_path: Option<String>,
+    /// Full path of the type, when it is documented in this run.
+    pub type_path: Option<String>,
+    /// The impl block; `None` for a derive.
+    pub impl_item: Option<&'a NoirImpl>,
+}
+
+/// A public function, method or field whose signature or type mentions the type at `type_path`,
+/// directly or through a type alias.
+#[derive(Debug, Clone)]
+pub struct TypeUse {
+    /// Full path of the used type or type alias.
+    pub type_path: String,
+    /// `function`, `method`, `trait method`, `field` or `type alias`.
+    pub kind: &'static str,
+    /// Full path of the user, e.g. `aztec::account::AccountActions::entrypoint`.
+    pub path: String,
+}
+
+impl<'a> LibraryPages<'a> {
+    // Page an item of `module` is documented on, and its anchor there unless it has a page of its own
+    pub(crate) fn item_location(&self, module: usize, anchor: &str) -> (String, Option<String>) {
+        match self.item_pages.iter().find(|item_page| item_page.module == module && item_page.item.anchor() == anchor) {
+            Some(item_page) => (item_page.id.clone(), None),
+            None => (self.module_pages[module].0.clone(), Some(anchor.to_string())),
+        }
+    }
+
+    pub(crate) fn impl_page(&self, impl_doc: &ImplDoc) -> String {
+        match impl_doc.owner {
+            Some((module, name)) => self.item_location(module, &inventory::item_anchor("struct", &[name])).0,
+            None => self.module_pages[impl_doc.module].0.clone(),
+        }
+    }
+
+    // Inherent impls of the struct `name` in `module`
+    pub(crate) fn struct_impls(&self, module: usize, name: &str) -> Vec<&ImplDoc<'a>> {
+        self.impl_docs.iter().filter(|impl_doc| impl_doc.owner == Some((module, name))).collect()
+    }
+
+    // Traits implemented by the type at `path`
+    pub(crate) fn implemented_traits(&self, path: &str) -> Vec<&TraitImpl<'a>> {
+        self.trait_impls.iter().filter(|trait_impl| trait_impl.type_path.as_deref() == Some(path)).collect()
+    }
+
+    // Functions, methods and fields that accept, return or hold the type at `path`
+    pub(crate) fn type_uses(&self, path: &str) -> Vec<&TypeUse> {
+        self.type_uses.iter().filter(|type_use| type_use.type_path == path).collect()
+    }
+
+    // Implementations of the trait at `path`
+    pub(crate) fn implementors(&self, path: &str) -> Vec<&TraitImpl<'a>> {
+        self.trait_impls.iter().filter(|trait_impl| trait_impl.trait_path.as_deref() == Some(path)).collect()
+    }
+
+    // Impls shown in a module page's own Implementations section
+    pub(crate) fn module_impls(&self, module: usize) -> Vec<&ImplDoc<'a>> {
+        self.impl_docs.iter().filter(|impl_doc| impl_doc.owner.is_none() && impl_doc.module == module).collect()
     }
+}
+
+/// The page of a single item.
+#[derive(Debug)]
+pub struct ItemPage<'a> {
+    /// Doc ID, e.g. `aztec/context/private_context/struct-PrivateContext`.
+    pub id: String,
+    /// Index of the item's module in `module_pages`.
+    pub module: usize,
+    /// The documented item.
+    pub item: PageItem<'a>,
+}
 
-    (docs, sidebar_items)
+/// An item listed on a module page.
+#[derive(Debug, Clone, Copy)]
+pub enum PageItem<'a> {
+    /// A struct.
+    Struct(&'a NoirStruct),
+    /// A trait.
+    Trait(&'a NoirTrait),
+    /// A type alias.
+    TypeAlias(&'a NoirTypeAlias),
+    /// A free function.
+    Function(&'a NoirFunction),
+    /// A constant.
+    Constant(&'a NoirConstant),
 }
 
-fn generate_aztec_overview(aztec_library: &Library) -> String {
-    let mut content = String::from("# Aztec Library\n\n");
-    content.push_str("The Aztec library is the core component of the Aztec.nr project. It contains the following modules:\n\n");
-    
-    for file in &aztec_library.files {
-        content.push_str(&format!("- [{}]({})\n", file.name, file.name));
+impl PageItem<'_> {
+    /// The item's name.
+    pub fn name(&self) -> &str {
+        match self {
+            PageItem::Struct(struct_item) => &struct_item.name,
+            PageItem::Trait(trait_item) => &trait_item.name,
+            PageItem::TypeAlias(type_alias) => &type_alias.name,
+            PageItem::Function(function) => &function.name,
+            PageItem::Constant(constant) => &constant.name,
+        }
+    }
+
+    /// The item's kind, capitalized, e.g. for page titles and summary tables.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PageItem::Struct(_) => "Struct",
+            PageItem::Trait(_) => "Trait",
+            PageItem::TypeAlias(_) => "Type Alias",
+            PageItem::Function(_) => "Function",
+            PageItem::Constant(_) => "Constant",
+        }
+    }
+
+    /// The item's anchor on its module page, which also names its own page.
+    pub fn anchor(&self) -> String {
+        match self {
+            PageItem::Struct(struct_item) => inventory::item_anchor("struct", &[&struct_item.name]),
+            PageItem::Trait(trait_item) => inventory::item_anchor("trait", &[&trait_item.name]),
+            PageItem::TypeAlias(type_alias) => inventory::item_anchor("type", &[&type_alias.name]),
+            PageItem::Function(function) => inventory::item_anchor("fn", &[&function.name]),
+            PageItem::Constant(constant) => inventory::item_anchor("constant", &[&constant.name]),
+        }
     }
 
-    content
+    /// The item's doc comment.
+    pub fn doc_comment(&self) -> Option<&str> {
+        match self {
+            PageItem::Struct(struct_item) => struct_item.doc_comment.as_deref(),
+            PageItem::Trait(trait_item) => trait_item.doc_comment.as_deref(),
+            PageItem::TypeAlias(type_alias) => type_alias.doc_comment.as_deref(),
+            PageItem::Function(function) => function.doc_comment.as_deref(),
+            PageItem::Constant(constant) => constant.doc_comment.as_deref(),
+        }
+    }
 }
 
-fn generate_file_doc(file: &NoirFile) -> String {
-    let mut content = String::from(&format!("# {}\n\n", file.name));
-    content.push_str(&generate_file_content(file));
-    content
+// Every item of a file in the order its module page lists them: grouped by kind, unless
+// ordered alphabetically
+pub(crate) fn file_items(file: &NoirFile, order: OrderStrategy) -> Vec<PageItem<'_>> {
+    let mut items: Vec<PageItem> = file.structs.iter().map(PageItem::Struct).collect();
+    items.extend(file.traits.iter().map(PageItem::Trait));
+    items.extend(file.type_aliases.iter().map(PageItem::TypeAlias));
+    items.extend(file.functions.iter().map(PageItem::Function));
+    items.extend(file.constants.iter().map(PageItem::Constant));
+    if order == OrderStrategy::Alphabetical {
+        items.sort_by(|a, b| a.name().cmp(b.name()));
+    }
+    items
 }
 
-fn generate_file_content(file: &NoirFile) -> String {
-    let mut content = String::new();
-    
-    // Add file-level description
-    content.push_str(&format!("# {} Module\n\n", file.name));
-    content.push_str("This module contains the following components:\n\n");
-    
-    // Generate table of contents
-    content.push_str("## Table of Contents\n");
-    if !file.structs.is_empty() { content.push_str("- [Structs](#structs)\n"); }
-    if !file.traits.is_empty() { content.push_str("- [Traits](#traits)\n"); }
-    if !file.functions.is_empty() { content.push_str("- [Functions](#functions)\n"); }
-    if !file.impls.is_empty() { content.push_str("- [Implementations](#implementations)\n"); }
-    content.push_str("\n");
+fn impl_docs<'a>(pages: &LibraryPages<'a>) -> Vec<ImplDoc<'a>> {
+    let mut impl_docs = Vec::new();
+    for (module, (_, file)) in pages.module_pages.iter().enumerate() {
+        for impl_item in &file.impls {
+            let owner = match impl_item.trait_name {
+                Some(_) => None,
+                None => impl_owner(pages, module, &inventory::base_type_path(&impl_item.target)),
+            };
+            impl_docs.push(ImplDoc {
+                impl_item,
+                module,
+                owner,
+                anchor: String::new(),
+                method_anchors: Vec::new(),
+            });
+        }
+    }
+
+    // Repeated names on one page get a numeric suffix
+    let mut used: HashMap<String, HashSet<String>> = HashMap::new();
+    for impl_doc in &mut impl_docs {
+        let page = pages.impl_page(impl_doc);
+        let used = used.entry(page).or_default();
+        let mut claim = |anchor: String| {
+            let mut candidate = anchor.clone();
+            let mut suffix = 2;
+            while !used.insert(candidate.clone()) {
+                candidate = format!("{}-{}", anchor, suffix);
+                suffix += 1;
+            }
+            candidate
+        };
 
-    // Generate struct documentation
-    if !file.structs.is_empty() {
-        content.push_str("## Structs\n\n");
+        let target = inventory::base_type_path(&impl_doc.impl_item.target);
+        let target = target.rsplit("::").next().unwrap_or(&target).to_string();
+        impl_doc.anchor = claim(inventory::item_anchor("impl", &[&target]));
+        impl_doc.method_anchors = impl_doc.impl_item.methods.iter().map(|method| claim(inventory::item_anchor("method", &[&target, &method.name]))).collect();
+    }
+
+    impl_docs
+}
+
+fn trait_impls<'a>(pages: &LibraryPages<'a>, resolver: &LinkResolver) -> Vec<TraitImpl<'a>> {
+    let mut trait_impls = Vec::new();
+    for (module, (_, file)) in pages.module_pages.iter().enumerate() {
+        let module_name = qualified_module_name(&pages.library.name, file);
         for struct_item in &file.structs {
-            content.push_str(&format!("### {}\n\n", struct_item.name));
-            // Add struct description, generic parameters, etc.
-            content.push_str("Fields:\n");
-            for field in &struct_item.fields {
-                content.push_str(&format!("- `{}`: {}\n", field.name, field.ty));
+            for derive in &struct_item.derives {
+                trait_impls.push(TraitImpl {
+                    trait_name: derive.clone(),
+                    type_name: struct_item.name.clone(),
+                    trait_path: item_path(pages, module, derive, resolver),
+                    type_path: Some(format!("{}::{}", module_name, struct_item.name)),
+                    impl_item: None,
+                });
             }
-            content.push_str("\n");
+        }
+        for impl_item in &file.impls {
+            let Some(trait_name) = &impl_item.trait_name else { continue };
+            let target = inventory::base_type_path(&impl_item.target);
+            let type_path = match impl_owner(pages, module, &target) {
+                Some((owner_module, name)) => Some(format!("{}::{}", qualified_module_name(&pages.library.name, pages.module_pages[owner_module].1), name)),
+                None => resolver.canonical_path(&target, &pages.library.name),
+            };
+            trait_impls.push(TraitImpl {
+                trait_name: trait_name.clone(),
+                type_name: impl_item.target.clone(),
+                trait_path: item_path(pages, module, trait_name, resolver),
+                type_path,
+                impl_item: Some(impl_item),
+            });
         }
     }
+    trait_impls
+}
 
-    // Generate trait documentation
-    if !file.traits.is_empty() {
-        content.push_str("## Traits\n\n");
-        for trait_item in &file.traits {
-            content.push_str(&format!("### {}\n\n", trait_item.name));
-            for method in &trait_item.methods {
-                content.push_str(&format!("#### `{}`\n\n", method.name));
-                if let Some(doc_comment) = &method.doc_comment {
-                    content.push_str(&format!("{}\n\n", doc_comment));
-                }
-                content.push_str("```rust\n");
-                content.push_str(&format!("fn {}(", method.name));
-                // Add parameters
-                content.push_str(")\n");
-                if let Some(return_type) = &method.return_type {
-                    content.push_str(&format!(" -> {}", return_type));
+// Full path of every type alias of the crate, with the paths of the types it names
+fn alias_targets(pages: &LibraryPages, resolver: &LinkResolver) -> Vec<(String, Vec<String>)> {
+    let mut targets = Vec::new();
+    for (module, (_, file)) in pages.module_pages.iter().enumerate() {
+        let module_name = qualified_module_name(&pages.library.name, file);
+        for type_alias in &file.type_aliases {
+            let paths = type_names(&type_alias.ty).iter().filter_map(|name| item_path(pages, module, name, resolver)).collect();
+            targets.push((format!("{}::{}", module_name, type_alias.name), paths));
+        }
+    }
+    targets
+}
+
+// Every public function, method and type alias, and method of a public trait or public field of
+// a public struct, whose type mentions a struct, trait or type alias known to the run. Uses
+// through an alias count for the aliased types too: a field of type `Context`, where
+// `type Context = PrivateContext`, uses both `Context` and `PrivateContext`.
+fn type_uses(pages: &LibraryPages, resolver: &LinkResolver, aliases: &HashMap<String, Vec<String>>) -> Vec<TypeUse> {
+    let mut uses = Vec::new();
+    let mut push = |module: usize, types: Vec<&str>, self_type: Option<&str>, kind: &'static str, path: String| {
+        let mut type_paths: Vec<String> = types
+            .into_iter()
+            .flat_map(type_names)
+            .filter_map(|name| match (name.as_str(), self_type) {
+                ("Self", Some(self_type)) => item_path(pages, module, self_type, resolver),
+                _ => item_path(pages, module, &name, resolver),
+            })
+            .collect();
+        // Aliases of aliases are followed too; the check against `type_paths` stops cycles
+        let mut index = 0;
+        while index < type_paths.len() {
+            for target in aliases.get(&type_paths[index]).into_iter().flatten() {
+                if !type_paths.contains(target) {
+                    type_paths.push(target.clone());
                 }
-                content.push_str("\n```\n\n");
             }
+            index += 1;
         }
-    }
+        type_paths.retain(|type_path| *type_path != path);
+        type_paths.sort();
+        type_paths.dedup();
+        for type_path in type_paths {
+            uses.push(TypeUse { type_path, kind, path: path.clone() });
+        }
+    };
 
-    // Generate function documentation
-    if !file.functions.is_empty() {
-        content.push_str("## Functions\n\n");
-        for function in &file.functions {
-            content.push_str(&format!("### `{}`\n\n", function.name));
-            if let Some(doc_comment) = &function.doc_comment {
-                content.push_str(&format!("{}\n\n", doc_comment));
+    for (module, (_, file)) in pages.module_pages.iter().enumerate() {
+        let module_name = qualified_module_name(&pages.library.name, file);
+        for struct_item in file.structs.iter().filter(|struct_item| struct_item.is_public) {
+            for field in struct_item.fields.iter().filter(|field| field.is_public) {
+                push(module, vec![field.ty.as_str()], None, "field", format!("{}::{}::{}", module_name, struct_item.name, field.name));
             }
-            content.push_str("```rust\n");
-            content.push_str(&format!("fn {}(", function.name));
-            // Add parameters
-            content.push_str(")\n");
-            if let Some(return_type) = &function.return_type {
-                content.push_str(&format!(" -> {}", return_type));
+        }
+        for trait_item in file.traits.iter().filter(|trait_item| trait_item.is_public) {
+            for method in &trait_item.methods {
+                push(module, signature_types(method), None, "trait method", format!("{}::{}::{}", module_name, trait_item.name, method.name));
             }
-            content.push_str("\n```\n\n");
+        }
+        for function in file.functions.iter().filter(|function| function.is_public) {
+            push(module, signature_types(function), None, "function", format!("{}::{}", module_name, function.name));
+        }
+        for type_alias in file.type_aliases.iter().filter(|type_alias| type_alias.is_public) {
+            push(module, vec![type_alias.ty.as_str()], None, "type alias", format!("{}::{}", module_name, type_alias.name));
+        }
+    }
+    for impl_doc in &pages.impl_docs {
+        let target_path = inventory::method_parent_path(pages, impl_doc);
+        for method in impl_doc.impl_item.methods.iter().filter(|method| method.is_public) {
+            push(impl_doc.module, signature_types(method), Some(&impl_doc.impl_item.target), "method", format!("{}::{}", target_path, method.name));
         }
     }
+    uses
+}
 
-    // Generate impl documentation
-    if !file.impls.is_empty() {
-        content.push_str("## Implementations\n\n");
-        for impl_item in &file.impls {
-            content.push_str(&format!("### Impl for {}\n\n", impl_item.target));
-            for method in &impl_item.methods {
-                content.push_str(&format!("#### `{}`\n\n", method.name));
-                if let Some(doc_comment) = &method.doc_comment {
-                    let (description, params) = parse_doc_comment(doc_comment);
-                    content.push_str(&format!("{}\n\n", description));
-
-                    // Generate parameter table
-                    if !params.is_empty() {
-                        content.push_str("| Parameter | Type | Description |\n");
-                        content.push_str("|-----------|------|-------------|\n");
-                        for (name, _, desc) in params {
-                            let param_type = method.params.iter()
-                                .find(|p| p.name == name)
-                                .map(|p| p.ty.clone())
-                                .unwrap_or_else(|| "Unknown".to_string());
-                            content.push_str(&format!("| `{}` | `{}` | {} |\n", name, param_type, desc));
-                        }
-                        content.push_str("\n");
-                    }
+fn signature_types(function: &NoirFunction) -> Vec<&str> {
+    let mut types: Vec<&str> = function.params.iter().map(|param| param.ty.as_str()).collect();
+    types.extend(function.return_type.as_deref());
+    types
+}
+
+static TYPE_NAME_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)*").unwrap());
+
+// Every path in a type, e.g. `BoundedVec < Note , 4 >` -> `BoundedVec`, `Note`
+fn type_names(ty: &str) -> Vec<String> {
+    let ty = ty.replace(" :: ", "::");
+    TYPE_NAME_REGEX
+        .find_iter(&ty)
+        .map(|name| name.as_str().to_string())
+        .collect()
+}
+
+// Full path of the struct, trait or type alias `name` refers to in `module`: one declared there,
+// else whatever the path names across the documented crates
+fn item_path(pages: &LibraryPages, module: usize, name: &str, resolver: &LinkResolver) -> Option<String> {
+    let name = inventory::base_type_path(name);
+    let file = pages.module_pages[module].1;
+    let declared = file.structs.iter().any(|struct_item| struct_item.name == name)
+        || file.traits.iter().any(|trait_item| trait_item.name == name)
+        || file.type_aliases.iter().any(|type_alias| type_alias.name == name);
+    if declared {
+        return Some(format!("{}::{}", qualified_module_name(&pages.library.name, file), name));
+    }
+    resolver.canonical_path(&name, &pages.library.name)
+}
+
+// The struct an inherent impl for `target` belongs to: one in the same module, else the only
+// struct of that name in the crate, else the one whose path ends with `target`
+pub(crate) fn impl_owner<'a>(pages: &LibraryPages<'a>, module: usize, target: &str) -> Option<(usize, &'a str)> {
+    let name = target.rsplit("::").next().unwrap_or(target);
+    let candidates: Vec<(usize, &'a str)> = pages
+        .module_pages
+        .iter()
+        .enumerate()
+        .flat_map(|(index, (_, file))| file.structs.iter().filter(|struct_item| struct_item.name == name).map(move |struct_item| (index, struct_item.name.as_str())))
+        .collect();
+
+    if let Some(candidate) = candidates.iter().find(|(index, _)| *index == module) {
+        return Some(*candidate);
+    }
+    if candidates.len() == 1 {
+        return Some(candidates[0]);
+    }
+    let target = target.strip_prefix("crate::").unwrap_or(target);
+    candidates.into_iter().find(|(index, name)| {
+        let path = format!("{}::{}", qualified_module_name(&pages.library.name, pages.module_pages[*index].1), name);
+        path.ends_with(&format!("::{}", target))
+    })
+}
+
+fn item_pages<'a>(module_pages: &[(String, &'a NoirFile)], order: OrderStrategy, used_ids: &mut HashSet<String>) -> Vec<ItemPage<'a>> {
+    let mut pages = Vec::new();
+    for (module, (module_id, file)) in module_pages.iter().enumerate() {
+        for item in file_items(file, order) {
+            pages.push(ItemPage {
+                id: unique_doc_id(format!("{}/{}", module_id, item.anchor()), used_ids),
+                module,
+                item,
+            });
+        }
+    }
+    pages
+}
+
+// Autogenerated Docusaurus sidebars take a folder's `index` page as the folder's category page, so
+// a module page with pages below it, e.g. `aztec/utils` beside `aztec/utils/math`, moves into its
+// folder as `aztec/utils/index` rather than being listed next to the folder as well
+fn move_into_folders(module_pages: &mut [(String, &NoirFile)], item_pages: &[ItemPage], used_ids: &mut HashSet<String>) {
+    let ids: Vec<String> = module_pages.iter().map(|(id, _)| id.clone()).chain(item_pages.iter().map(|item_page| item_page.id.clone())).collect();
+    for (id, _) in module_pages.iter_mut() {
+        let folder = format!("{}/", id);
+        let index_id = format!("{}index", folder);
+        if ids.iter().any(|other| other.starts_with(&folder)) && used_ids.insert(index_id.clone()) {
+            *id = index_id;
+        }
+    }
+}
+
+struct CrateRoot {
+    name: String,
+    dir: PathBuf,
+    src_dir: PathBuf,
+}
+
+impl CrateRoot {
+    // Modules are named from `src`; files elsewhere in the crate, e.g. `tests/t.nr`, from the
+    // crate's own directory
+    fn module_path(&self, path: &Path) -> Vec<String> {
+        let relative_path = path.strip_prefix(&self.src_dir).or_else(|_| path.strip_prefix(&self.dir)).unwrap_or(path);
+        module_path(relative_path)
+    }
+}
+
+fn parse_directory(root: &Path, dir: &Path, crate_root: &CrateRoot, source_filter: &SourceFilter, libraries: &mut Vec<Library>, errors: &mut Vec<Error>) {
+    // A directory with a `Nargo.toml` starts a new crate
+    let nested_crate;
+    let crate_root = match read_crate_name(dir) {
+        Some(name) if dir != root => {
+            nested_crate = CrateRoot {
+                name,
+                dir: dir.to_path_buf(),
+                src_dir: crate_src_dir(dir),
+            };
+            &nested_crate
+        }
+        _ => crate_root,
+    };
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            errors.push(Error::io(dir, e));
+            return;
+        }
+    };
+    let mut paths = Vec::new();
+    for entry in entries {
+        match entry {
+            Ok(entry) => paths.push(entry.path()),
+            Err(e) => errors.push(Error::io(dir, e)),
+        }
+    }
+    paths.sort();
+
+    for path in paths {
+        let relative_path = path.strip_prefix(root).unwrap_or(&path);
+        if path.is_file() && source_filter.is_included(relative_path) {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    errors.push(Error::io(&path, e));
+                    continue;
                 }
-                content.push_str("```rust\n");
-                content.push_str(&format!("fn {}(", method.name));
-                // Add parameters
-                let params: Vec<String> = method.params.iter()
-                    .map(|p| format!("{}: {}", p.name, p.ty))
-                    .collect();
-                content.push_str(&params.join(", "));
-                content.push_str(")");
-                if let Some(return_type) = &method.return_type {
-                    content.push_str(&format!(" -> {}", return_type));
+            };
+            if filter::has_skip_marker(&content) {
+                continue;
+            }
+
+            match crate::parser::parse_noir_file(&path) {
+                Ok(mut noir_file) => {
+                    noir_file.module_path = crate_root.module_path(&path);
+                    noir_file.path = relative_path.to_path_buf();
+                    match libraries.iter_mut().find(|library| library.name == crate_root.name) {
+                        Some(library) => library.files.push(noir_file),
+                        None => libraries.push(Library {
+                            name: crate_root.name.clone(),
+                            root: root.to_path_buf(),
+                            repository_url: None,
+                            files: vec![noir_file],
+                        }),
+                    }
                 }
-                content.push_str("\n```\n\n");
+                Err(e) => errors.push(e),
             }
+        } else if path.is_dir() && !source_filter.is_excluded_dir(relative_path) {
+            parse_directory(root, &path, crate_root, source_filter, libraries, errors);
         }
     }
+}
 
-    content
+fn read_crate_name(dir: &Path) -> Option<String> {
+    let manifest = fs::read_to_string(dir.join("Nargo.toml")).ok()?;
+    let manifest: toml::Value = toml::from_str(&manifest).ok()?;
+    manifest.get("package")?.get("name")?.as_str().map(|name| name.to_string())
+}
 
+fn crate_src_dir(dir: &Path) -> PathBuf {
+    let src_dir = dir.join("src");
+    if src_dir.is_dir() { src_dir } else { dir.to_path_buf() }
 }
 
-pub fn write_docusaurus_docs(docs: Vec<DocusaurusDoc>, sidebar: Vec<SidebarItem>, output_dir: &str) -> std::io::Result<()> {
-    let docs_dir = Path::new(output_dir).join("docs");
-    fs::create_dir_all(&docs_dir)?;
+fn dir_name(dir: &Path) -> String {
+    dir.canonicalize()
+        .ok()
+        .and_then(|dir| dir.file_name().and_then(|s| s.to_str()).map(|s| s.to_string()))
+        .unwrap_or_else(|| "crate".to_string())
+}
 
-    for doc in docs {
-        let file_path = docs_dir.join(doc.path);
-        if let Some(parent) = file_path.parent() {
-            fs::create_dir_all(parent)?;
+// `context/private_context.nr` -> ["context", "private_context"]; `lib.nr`, `main.nr` and `mod.nr` name their directory
+fn module_path(relative_path: &Path) -> Vec<String> {
+    let mut segments: Vec<String> = relative_path
+        .with_extension("")
+        .components()
+        .filter_map(|component| component.as_os_str().to_str().map(|s| s.to_string()))
+        .collect();
+    if let Some(last) = segments.last() {
+        let is_root_file = segments.len() == 1 && (last == "lib" || last == "main");
+        if is_root_file || last == "mod" {
+            segments.pop();
         }
-        fs::write(file_path, doc.content)?;
     }
+    segments
+}
 
-    // Generate sidebar.js
-    let sidebar_content = generate_sidebar_js(&sidebar);
-    let sidebar_path = Path::new(output_dir).join("sidebars.js");
-    fs::write(sidebar_path, sidebar_content)?;
-
-    Ok(())
+fn module_doc_id(library: &Library, file: &NoirFile) -> String {
+    if file.module_path.is_empty() {
+        format!("{}/{}", library.name, file.name)
+    } else {
+        format!("{}/{}", library.name, file.module_path.join("/"))
+    }
 }
 
-fn generate_sidebar_js(sidebar: &[SidebarItem]) -> String {
-    let mut content = String::from("module.exports = {\n  someSidebar: [\n");
+pub(crate) fn qualified_module_name(crate_name: &str, file: &NoirFile) -> String {
+    let mut segments = vec![crate_name.to_string()];
+    segments.extend(file.module_path.iter().cloned());
+    segments.join("::")
+}
 
-    for item in sidebar {
-        content.push_str(&format_sidebar_item(item, 4));
+fn unique_doc_id(id: String, used_ids: &mut HashSet<String>) -> String {
+    let mut candidate = id.clone();
+    let mut suffix = 2;
+    while !used_ids.insert(candidate.clone()) {
+        candidate = format!("{}-{}", id, suffix);
+        suffix += 1;
     }
-
-    content.push_str("  ],\n};\n");
-    content
+    candidate
 }
 
-fn format_sidebar_item(item: &SidebarItem, indent: usize) -> String {
-    let spaces = " ".repeat(indent);
-    match item {
-        SidebarItem::Category { label, items } => {
-            let mut content = format!("{}{{type: 'category', label: '{}', items: [\n", spaces, label);
-            for sub_item in items {
-                content.push_str(&format_sidebar_item(sub_item, indent + 2));
-            }
-            content.push_str(&format!("{}]}},\n", spaces));
-            content
-        }
-        SidebarItem::Doc { id, label } => {
-            format!("{}{{type: 'doc', id: '{}', label: '{}'}},\n", spaces, id, label)
-        }
+#[cfg(test)]
+mod tests {
+    use super::doc_summary;
+
+    #[test]
+    fn summaries_end_at_the_first_sentence() {
+        assert_eq!(doc_summary("Returns the sender. Fails when called twice."), "Returns the sender.");
+        assert_eq!(doc_summary("Reads a note\nfrom storage.\n\nMore details."), "Reads a note from storage.");
+        assert_eq!(doc_summary(" * Packs a value.\n * Or fails."), "Packs a value.");
+    }
+
+    #[test]
+    fn summaries_skip_abbreviations_and_lowercase_continuations() {
+        assert_eq!(doc_summary("Capacity of a <Storage>, i.e. {16} | 2^4."), "Capacity of a <Storage>, i.e. {16} | 2^4.");
+        assert_eq!(doc_summary("Hashes inputs, e.g. Notes or nullifiers. Then stores them."), "Hashes inputs, e.g. Notes or nullifiers.");
+        assert_eq!(doc_summary("Uses v1. the legacy scheme."), "Uses v1. the legacy scheme.");
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,352 @@
+use crate::error::Error;
+use crate::generator::{ImplDoc, LibraryPages};
+use crate::render::OutputFile;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+const INVENTORY_DIR: &str = "inventory";
+pub(crate) const INVENTORY_VERSION: u32 = 1;
+
+/// The documented items of a crate and where their documentation is, written to
+/// `inventory/<crate>.json` for other documentation runs to link to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Inventory {
+    /// Format version of the inventory file.
+    pub version: u32,
+    /// The crate's name.
+    pub crate_name: String,
+    /// Route the pages are served under, appended to the base URL given by consumers.
+    #[serde(default)]
+    pub base_path: String,
+    /// Extension the pages are served with, e.g. `html`; absent when they are served by doc ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_extension: Option<String>,
+    /// Every documented item.
+    pub items: Vec<InventoryItem>,
+}
+
+/// An item in an [`Inventory`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InventoryItem {
+    /// Full path, e.g. `aztec::context::private_context::PrivateContext`. Methods of trait impls
+    /// are listed under the trait, e.g. `aztec::note::Note::Serialize::serialize`.
+    pub path: String,
+    /// `module`, `struct`, `trait`, `trait_method`, `type_alias`, `function`, `constant` or
+    /// `method`.
+    pub kind: String,
+    /// Doc ID of the page the item is documented on, served as `<page>.<page_extension>` when
+    /// the inventory has a page extension.
+    pub page: String,
+    /// Heading ID on that page; absent when the item has a page of its own.
+    pub anchor: Option<String>,
+}
+
+pub(crate) struct ExternalInventory {
+    pub base_url: String,
+    pub inventory: Inventory,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum LinkTarget {
+    External(String),
+    // A page file generated in this run, linked relative to the page that refers to it
+    Local { page: String, anchor: Option<String> },
+}
+
+/// Resolves item paths, as written in doc comments and signatures, to links between pages.
+#[derive(Debug, Clone, Default)]
+pub struct LinkResolver {
+    targets: HashMap<String, LinkTarget>,
+    // Last path segments, `None` once two different items share one
+    short_names: HashMap<String, Option<String>>,
+}
+
+// Modules and items with a page of their own link to it without an anchor; their members, such
+// as trait methods, link to an anchor on it
+pub(crate) fn build_inventory(base_path: &str, page_extension: Option<&str>, pages: &LibraryPages) -> Inventory {
+    let crate_name = &pages.library.name;
+    let mut items = Vec::new();
+
+    for (module, (doc_id, file)) in pages.module_pages.iter().enumerate() {
+        let module_path = crate::generator::qualified_module_name(crate_name, file);
+        items.push(InventoryItem {
+            path: module_path.clone(),
+            kind: "module".to_string(),
+            page: doc_id.clone(),
+            anchor: None,
+        });
+        let mut push = |name: &str, kind: &str, (page, anchor): (String, Option<String>)| {
+            items.push(InventoryItem {
+                path: format!("{}::{}", module_path, name),
+                kind: kind.to_string(),
+                page,
+                anchor,
+            });
+        };
+
+        for struct_item in &file.structs {
+            push(&struct_item.name, "struct", pages.item_location(module, &item_anchor("struct", &[&struct_item.name])));
+        }
+        for trait_item in &file.traits {
+            let (page, anchor) = pages.item_location(module, &item_anchor("trait", &[&trait_item.name]));
+            push(&trait_item.name, "trait", (page.clone(), anchor));
+            for method in &trait_item.methods {
+                let name = format!("{}::{}", trait_item.name, method.name);
+                push(&name, "trait_method", (page.clone(), Some(item_anchor("tymethod", &[&trait_item.name, &method.name]))));
+            }
+        }
+        for type_alias in &file.type_aliases {
+            push(&type_alias.name, "type_alias", pages.item_location(module, &item_anchor("type", &[&type_alias.name])));
+        }
+        for function in &file.functions {
+            push(&function.name, "function", pages.item_location(module, &item_anchor("fn", &[&function.name])));
+        }
+        for constant in &file.constants {
+            push(&constant.name, "constant", pages.item_location(module, &item_anchor("constant", &[&constant.name])));
+        }
+    }
+
+    // Methods belong to the struct's path, wherever the impl block is written
+    for impl_doc in &pages.impl_docs {
+        let page = pages.impl_page(impl_doc);
+        let parent_path = method_parent_path(pages, impl_doc);
+        for (method, anchor) in impl_doc.impl_item.methods.iter().zip(&impl_doc.method_anchors) {
+            items.push(InventoryItem {
+                path: format!("{}::{}", parent_path, method.name),
+                kind: "method".to_string(),
+                page: page.clone(),
+                anchor: Some(anchor.clone()),
+            });
+        }
+    }
+
+    Inventory {
+        version: INVENTORY_VERSION,
+        crate_name: crate_name.clone(),
+        base_path: base_path.to_string(),
+        page_extension: page_extension.map(str::to_string),
+        items,
+    }
+}
+
+// Each crate's inventory is written to `inventory/<crate>.json`
+pub(crate) fn inventory_files(inventories: &[Inventory]) -> Vec<OutputFile> {
+    inventories
+        .iter()
+        .map(|inventory| OutputFile {
+            path: Path::new(INVENTORY_DIR).join(format!("{}.json", inventory.crate_name)),
+            content: serde_json::to_string_pretty(inventory).expect("inventories serialize to JSON"),
+        })
+        .collect()
+}
+
+pub(crate) fn load_external_inventory(path: &Path, base_url: &str) -> Result<ExternalInventory, Error> {
+    let content = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    let inventory: Inventory = serde_json::from_str(&content).map_err(|e| Error::Resolution {
+        path: Some(path.to_path_buf()),
+        message: format!("invalid inventory: {}", e),
+    })?;
+    if inventory.version != INVENTORY_VERSION {
+        return Err(Error::Resolution {
+            path: Some(path.to_path_buf()),
+            message: format!("unsupported inventory version {}", inventory.version),
+        });
+    }
+
+    Ok(ExternalInventory {
+        base_url: base_url.trim_end_matches('/').to_string(),
+        inventory,
+    })
+}
+
+impl LinkResolver {
+    pub(crate) fn new(externals: &[ExternalInventory]) -> Self {
+        let mut resolver = LinkResolver::default();
+
+        for external in externals {
+            let root = format!("{}/{}", external.base_url, external.inventory.base_path.trim_matches('/'));
+            let root = root.trim_end_matches('/');
+            for item in &external.inventory.items {
+                let page = match &external.inventory.page_extension {
+                    Some(extension) => format!("{}/{}.{}", root, item.page, extension),
+                    None => format!("{}/{}", root, item.page),
+                };
+                let url = match &item.anchor {
+                    Some(anchor) => format!("{}#{}", page, anchor),
+                    None => page,
+                };
+                resolver.insert(&item.path, LinkTarget::External(url));
+            }
+        }
+
+        resolver
+    }
+
+    // A copy that also resolves the items documented in this run, whose pages are written as
+    // `<page>.<page_extension>`
+    pub(crate) fn with_local(&self, inventories: &[Inventory], page_extension: &str) -> Self {
+        let mut resolver = self.clone();
+        for inventory in inventories {
+            for item in &inventory.items {
+                resolver.insert(
+                    &item.path,
+                    LinkTarget::Local {
+                        page: format!("{}.{}", item.page, page_extension),
+                        anchor: item.anchor.clone(),
+                    },
+                );
+            }
+        }
+        resolver
+    }
+
+    fn insert(&mut self, path: &str, target: LinkTarget) {
+        let short_name = path.rsplit("::").next().unwrap_or(path).to_string();
+        self.short_names
+            .entry(short_name)
+            .and_modify(|existing| {
+                if existing.as_deref() != Some(path) {
+                    *existing = None;
+                }
+            })
+            .or_insert_with(|| Some(path.to_string()));
+        self.targets.insert(path.to_string(), target);
+    }
+
+    pub(crate) fn resolve(&self, path: &str, crate_name: &str) -> Option<&LinkTarget> {
+        self.targets.get(&self.canonical_path(path, crate_name)?)
+    }
+
+    // Full path of the item `path` refers to from within `crate_name`, e.g. `PrivateContext` ->
+    // `aztec::context::private_context::PrivateContext`. `crate::` paths only resolve in that crate.
+    pub(crate) fn canonical_path(&self, path: &str, crate_name: &str) -> Option<String> {
+        let path: String = path.split_whitespace().collect();
+        let path = path.strip_prefix("dep::").unwrap_or(&path);
+        if let Some(rest) = path.strip_prefix("crate::") {
+            let path = format!("{}::{}", crate_name, rest);
+            return self.targets.contains_key(&path).then_some(path);
+        }
+        if self.targets.contains_key(path) {
+            return Some(path.to_string());
+        }
+        // A short name is only usable on its own if no other item shares it
+        self.short_names.get(path)?.clone()
+    }
+
+    /// URL of the item at `path`, as written in crate `crate_name`, seen from the page `from_page`.
+    pub fn url(&self, path: &str, crate_name: &str, from_page: &str) -> Option<String> {
+        match self.resolve(path, crate_name)? {
+            LinkTarget::External(url) => Some(url.clone()),
+            LinkTarget::Local { page, anchor } => {
+                let depth = from_page.matches('/').count();
+                let link = format!("{}{}", "../".repeat(depth), page);
+                Some(match anchor {
+                    Some(anchor) => format!("{}#{}", link, anchor),
+                    None => link,
+                })
+            }
+        }
+    }
+
+    /// Renders a type as inline code, linking it when its base path is known.
+    pub fn link_type(&self, ty: &str, crate_name: &str, from_page: &str) -> String {
+        match self.url(&base_type_path(ty), crate_name, from_page) {
+            Some(url) => format!("[`{}`]({})", ty, url),
+            None => format!("`{}`", ty),
+        }
+    }
+
+    /// Links intra-doc links (`[`path`]`, `[path]`) and inline code spans in prose that name a
+    /// known item. Unresolved intra-doc links are left as code.
+    pub fn link_code_spans(&self, text: &str, crate_name: &str, from_page: &str) -> String {
+        let text = INTRA_DOC_LINK_REGEX.replace_all(text, |captures: &regex::Captures| {
+            let link = captures.get(1).unwrap();
+            let path = link.as_str().trim_matches('`');
+            let trailing = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+            match self.url(path, crate_name, from_page) {
+                Some(url) => format!("[`{}`]({}){}", path, url, trailing),
+                None => format!("`{}`{}", path, trailing),
+            }
+        });
+
+        CODE_SPAN_REGEX
+            .replace_all(&text, |captures: &regex::Captures| {
+                let prefix = captures.get(1).unwrap().as_str();
+                let name = captures.get(2).unwrap().as_str();
+                match self.url(name, crate_name, from_page) {
+                    Some(url) => format!("{}[`{}`]({})", prefix, name, url),
+                    None => captures.get(0).unwrap().as_str().to_string(),
+                }
+            })
+            .into_owned()
+    }
+}
+
+// `[`path`]` or `[path]` not followed by `(`, `[` or `:`, i.e. not already a Markdown link
+static INTRA_DOC_LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[(`[A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)*`|[A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)*)\]([^(\[:]|$)").unwrap()
+});
+
+// An inline code span holding just a path, not already inside a link
+static CODE_SPAN_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(^|[^\[])`([A-Za-z_][A-Za-z0-9_]*(?:::[A-Za-z_][A-Za-z0-9_]*)*)`").unwrap());
+
+// Paths referenced by intra-doc links in `text`
+pub(crate) fn intra_doc_links(text: &str) -> Vec<String> {
+    INTRA_DOC_LINK_REGEX
+        .captures_iter(text)
+        .map(|captures| captures.get(1).unwrap().as_str().trim_matches('`').to_string())
+        .collect()
+}
+
+// Stable heading IDs, e.g. `struct-AccountActions` or `method-AccountActions-entrypoint`
+pub(crate) fn item_anchor(kind: &str, names: &[&str]) -> String {
+    let mut anchor = kind.to_string();
+    for name in names {
+        anchor.push('-');
+        anchor.push_str(name);
+    }
+    anchor
+}
+
+// e.g. `aztec::account::AccountActions` for an impl, inherent or of a trait, of a struct in
+// another module. Types from other crates keep the path they are written with.
+pub(crate) fn impl_target_path(pages: &LibraryPages, impl_doc: &ImplDoc) -> String {
+    let target = base_type_path(&impl_doc.impl_item.target);
+    let module_name = |module: usize| crate::generator::qualified_module_name(&pages.library.name, pages.module_pages[module].1);
+    match impl_doc.owner.or_else(|| crate::generator::impl_owner(pages, impl_doc.module, &target)) {
+        Some((module, name)) => format!("{}::{}", module_name(module), name),
+        None => match (target.strip_prefix("dep::"), target.strip_prefix("crate::")) {
+            (Some(path), _) => path.to_string(),
+            (_, Some(path)) => format!("{}::{}", pages.library.name, path),
+            _ => format!("{}::{}", module_name(impl_doc.module), target),
+        },
+    }
+}
+
+// Path the methods of an impl block are listed under: the type's for inherent impls, and for
+// trait impls the type's followed by the trait, e.g. `aztec::note::Note::Serialize`, so two
+// traits with a method of the same name do not collide
+pub(crate) fn method_parent_path(pages: &LibraryPages, impl_doc: &ImplDoc) -> String {
+    let target_path = impl_target_path(pages, impl_doc);
+    match &impl_doc.impl_item.trait_name {
+        Some(trait_name) => {
+            let trait_path = base_type_path(trait_name);
+            format!("{}::{}", target_path, trait_path.rsplit("::").next().unwrap_or(&trait_path))
+        }
+        None => target_path,
+    }
+}
+
+// `& mut BoundedVec < Field , 4 >` -> `BoundedVec`
+pub(crate) fn base_type_path(ty: &str) -> String {
+    let without_generics = ty.split('<').next().unwrap_or(ty);
+    without_generics
+        .replace('&', "")
+        .split_whitespace()
+        .filter(|part| *part != "mut")
+        .collect()
+}
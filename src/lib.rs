@@ -0,0 +1,208 @@
+//! Documentation generator for Noir crates.
+//!
+//! The [`DocBuilder`] is the entry point for embedding the generator in other tools: point it at
+//! one or more directories of Noir sources, give it a [`Config`], and it returns both the parsed
+//! model and the rendered pages of the configured output format. Other formats plug in through
+//! the [`Renderer`] trait.
+//!
+//! ```no_run
+//! use noir_docs_generator::{Config, Diagnostic, DocBuilder};
+//!
+//! let config = Config::load("noirdoc.toml".as_ref())?;
+//! let builder = DocBuilder::new(config).input("aztec-nr");
+//! let documentation = builder.build()?;
+//!
+//! for library in &documentation.libraries {
+//!     println!("{}: {} modules", library.name, library.files.len());
+//! }
+//! for error in &documentation.errors {
+//!     eprintln!("{}", Diagnostic::from(error).render());
+//! }
+//! documentation.write(builder.config())?;
+//! # Ok::<(), noir_docs_generator::Error>(())
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod config;
+pub mod diagnostics;
+mod error;
+mod filter;
+mod generator;
+mod inventory;
+mod lint;
+mod model;
+mod ordering;
+mod parser;
+pub mod render;
+
+pub use config::Config;
+pub use diagnostics::Diagnostic;
+pub use error::Error;
+pub use generator::{ImplDoc, ItemPage, Library, LibraryPages, PageItem, Site, TraitImpl, TypeUse};
+pub use inventory::{Inventory, InventoryItem, LinkResolver};
+pub use ordering::OrderStrategy;
+pub use parser::{NoirConstant, NoirField, NoirFile, NoirFunction, NoirImpl, NoirParam, NoirStruct, NoirTrait, NoirTypeAlias, Span};
+pub use render::docusaurus::SidebarItem;
+pub use render::{OutputFile, Renderer};
+
+use std::path::PathBuf;
+
+/// Builds documentation for a set of input directories.
+pub struct DocBuilder {
+    config: Config,
+    inputs: Vec<PathBuf>,
+    models: Vec<PathBuf>,
+}
+
+/// The parsed documentation model together with everything rendered from it.
+#[derive(Debug)]
+pub struct Documentation {
+    /// One library per crate, in the configured order.
+    pub libraries: Vec<Library>,
+    /// Rendered files, with paths relative to the output directory.
+    pub files: Vec<OutputFile>,
+    /// Item inventories, one per crate, for linking from other documentation runs.
+    pub inventories: Vec<Inventory>,
+    /// Problems that did not stop the build, such as source files that failed to parse.
+    /// The affected files or links are left out of the output.
+    pub errors: Vec<Error>,
+    /// Documentation lint findings (missing docs, unknown `@param` tags, unresolved links),
+    /// when `features.lints` is enabled.
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl DocBuilder {
+    /// Creates a builder with no inputs.
+    pub fn new(config: Config) -> Self {
+        DocBuilder {
+            config,
+            inputs: Vec::new(),
+            models: Vec::new(),
+        }
+    }
+
+    /// Adds a directory of Noir sources. A directory may hold a single crate or several crates,
+    /// each marked by its `Nargo.toml`.
+    pub fn input(mut self, path: impl Into<PathBuf>) -> Self {
+        self.inputs.push(path.into());
+        self
+    }
+
+    /// Adds a documentation model exported by a previous run with the `json` output format, in
+    /// place of parsing sources. Models from several repositories can be combined this way.
+    pub fn model(mut self, path: impl Into<PathBuf>) -> Self {
+        self.models.push(path.into());
+        self
+    }
+
+    /// The configuration the builder renders with.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Parses every input into the documentation model without rendering it.
+    ///
+    /// Crates with the same name in different inputs or models are merged into one library when
+    /// they share a root and repository; crates of the same name from different places fail the
+    /// call, since their source links and diagnostics could only follow one of them. Files that
+    /// cannot be read or parsed are skipped and returned alongside the libraries; otherwise only an
+    /// invalid configuration, a missing input directory or an unreadable model fails the whole call.
+    pub fn parse(&self) -> Result<(Vec<Library>, Vec<Error>), Error> {
+        self.config.validate()?;
+        let source_filter = self.config.source_filter()?;
+
+        let mut libraries: Vec<Library> = Vec::new();
+        let mut errors = Vec::new();
+        for input in &self.inputs {
+            if !input.is_dir() {
+                return Err(Error::io(input, std::io::Error::new(std::io::ErrorKind::NotFound, "not a directory")));
+            }
+
+            let (input_libraries, input_errors) = generator::parse_libraries(input, &self.config, &source_filter);
+            errors.extend(input_errors);
+            merge_libraries(&mut libraries, input_libraries)?;
+        }
+        for model in &self.models {
+            merge_libraries(&mut libraries, model::load_model(model)?)?;
+        }
+
+        // Ordered once all inputs and models are merged
+        generator::order_libraries(&mut libraries, self.config.source.order);
+
+        Ok((libraries, errors))
+    }
+
+    /// Parses every input and renders it with the backend selected by `output.format`.
+    ///
+    /// External inventories that cannot be loaded are reported in [`Documentation::errors`] and
+    /// their links are left unresolved.
+    pub fn build(&self) -> Result<Documentation, Error> {
+        self.build_with(render::renderer(self.config.output.format).as_ref())
+    }
+
+    /// Parses every input and renders it with a custom backend.
+    pub fn build_with(&self, renderer: &dyn Renderer) -> Result<Documentation, Error> {
+        let (libraries, mut errors) = self.parse()?;
+
+        // Load inventories of externally documented crates
+        let mut externals = Vec::new();
+        for external in &self.config.externs {
+            match inventory::load_external_inventory(&external.inventory, &external.base_url) {
+                Ok(inventory) => externals.push(inventory),
+                Err(e) => errors.push(e),
+            }
+        }
+        let resolver = inventory::LinkResolver::new(&externals);
+
+        let (site, inventories) = generator::layout_site(&libraries, &self.config, renderer.served_extension());
+        let resolver = resolver.with_local(&inventories, renderer.page_extension());
+        let files = renderer.render(&site, &self.config, &resolver)?;
+
+        let warnings = if self.config.features.lints {
+            lint::lint_libraries(&libraries, &resolver)
+        } else {
+            Vec::new()
+        };
+
+        Ok(Documentation {
+            libraries,
+            files,
+            inventories,
+            errors,
+            warnings,
+        })
+    }
+}
+
+// File paths are relative to their library's root and source links to its repository, so only
+// crates from the same place can be merged
+fn merge_libraries(libraries: &mut Vec<Library>, new_libraries: Vec<Library>) -> Result<(), Error> {
+    for library in new_libraries {
+        match libraries.iter_mut().find(|existing| existing.name == library.name) {
+            Some(existing) if existing.root != library.root || existing.repository_url != library.repository_url => {
+                let origin = |library: &Library| match &library.repository_url {
+                    Some(repository_url) => format!("'{}' ({})", library.root.display(), repository_url),
+                    None => format!("'{}'", library.root.display()),
+                };
+                return Err(Error::Config {
+                    path: None,
+                    key: None,
+                    span: None,
+                    message: format!("crate `{}` comes from both {} and {}; document one of them or rename it", library.name, origin(existing), origin(&library)),
+                });
+            }
+            Some(existing) => existing.files.extend(library.files),
+            None => libraries.push(library),
+        }
+    }
+    Ok(())
+}
+
+impl Documentation {
+    /// Writes the rendered files and (when enabled) inventories to the configured output directory.
+    pub fn write(&self, config: &Config) -> Result<(), Error> {
+        let inventory_files = if config.features.inventory { inventory::inventory_files(&self.inventories) } else { Vec::new() };
+        render::write_output(self.files.iter().chain(&inventory_files), &config.output.dir)
+    }
+}
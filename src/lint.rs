@@ -0,0 +1,121 @@
+use crate::diagnostics::Diagnostic;
+use crate::generator::{parse_doc_comment, qualified_module_name, Library};
+use crate::inventory::{self, LinkResolver};
+use crate::parser::{NoirFile, NoirFunction, NoirStruct, Span};
+use std::path::Path;
+
+// Documentation problems that do not stop the build: missing docs, `@param` tags naming unknown
+// parameters and intra-doc links that resolve to nothing
+pub(crate) fn lint_libraries(libraries: &[Library], resolver: &LinkResolver) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for library in libraries {
+        // `crate::` links resolve within the crate they are written in
+        let resolves = |link: &str| resolver.resolve(link, &library.name).is_some();
+        for file in &library.files {
+            let path = library.root.join(&file.path);
+
+            // Modules may go undocumented, but their links must still resolve
+            if let Some(doc_comment) = &file.doc_comment {
+                lint_links(&path, &qualified_module_name(&library.name, file), doc_comment, None, &resolves, &mut diagnostics);
+            }
+
+            for struct_item in file.structs.iter().filter(|struct_item| struct_item.is_public) {
+                lint_doc_comment(&path, "struct", &struct_item.name, struct_item.doc_comment.as_deref(), struct_item.span, &resolves, &mut diagnostics);
+            }
+            for trait_item in file.traits.iter().filter(|trait_item| trait_item.is_public) {
+                lint_doc_comment(&path, "trait", &trait_item.name, trait_item.doc_comment.as_deref(), trait_item.span, &resolves, &mut diagnostics);
+            }
+
+            for type_alias in file.type_aliases.iter().filter(|type_alias| type_alias.is_public) {
+                lint_doc_comment(&path, "type alias", &type_alias.name, type_alias.doc_comment.as_deref(), type_alias.span, &resolves, &mut diagnostics);
+            }
+            for constant in file.constants.iter().filter(|constant| constant.is_public) {
+                lint_doc_comment(&path, "constant", &constant.name, constant.doc_comment.as_deref(), constant.span, &resolves, &mut diagnostics);
+            }
+
+            // Methods are only as public as their trait or type
+            let trait_methods = file.traits.iter().flat_map(|trait_item| trait_item.methods.iter().map(|method| (method, trait_item.is_public)));
+            let impl_methods = file.impls.iter().flat_map(|impl_item| {
+                let target_is_public = target_is_public(library, file, &impl_item.target);
+                impl_item.methods.iter().map(move |method| (method, target_is_public))
+            });
+            let functions = file.functions.iter().map(|function| (function, true));
+            for (function, parent_is_public) in functions.chain(trait_methods).chain(impl_methods) {
+                if function.is_public && parent_is_public {
+                    lint_doc_comment(&path, "function", &function.name, function.doc_comment.as_deref(), function.span, &resolves, &mut diagnostics);
+                }
+                lint_params(&path, function, &mut diagnostics);
+            }
+        }
+    }
+
+    diagnostics
+}
+
+fn lint_doc_comment(path: &Path, kind: &str, name: &str, doc_comment: Option<&str>, span: Span, resolves: &dyn Fn(&str) -> bool, diagnostics: &mut Vec<Diagnostic>) {
+    let doc_comment = match doc_comment {
+        Some(doc_comment) => doc_comment,
+        None => {
+            diagnostics.push(
+                Diagnostic::warning("missing-docs", format!("missing documentation for {} `{}`", kind, name))
+                    .at(path, span)
+                    .with_help("add a `///` doc comment describing it"),
+            );
+            return;
+        }
+    };
+    lint_links(path, name, doc_comment, Some(span), resolves, diagnostics);
+}
+
+// Module docs have no span of their own, so their warnings point at the file
+fn lint_links(path: &Path, name: &str, doc_comment: &str, span: Option<Span>, resolves: &dyn Fn(&str) -> bool, diagnostics: &mut Vec<Diagnostic>) {
+    for link in inventory::intra_doc_links(doc_comment) {
+        if !resolves(&link) {
+            diagnostics.push(Diagnostic {
+                path: Some(path.to_path_buf()),
+                span,
+                ..Diagnostic::warning("unresolved-link", format!("unresolved link to `{}` in the documentation of `{}`", link, name))
+                    .with_help("use the item's full path, or add an `[[extern]]` inventory for the crate that documents it")
+            });
+        }
+    }
+}
+
+// Impls of the crate's private structs need no docs; types from elsewhere are assumed public
+fn target_is_public(library: &Library, file: &NoirFile, target: &str) -> bool {
+    let target = inventory::base_type_path(target);
+    let name = target.rsplit("::").next().unwrap_or(&target);
+    let is_target = |struct_item: &&NoirStruct| struct_item.name == name;
+    if let Some(struct_item) = file.structs.iter().find(is_target) {
+        return struct_item.is_public;
+    }
+    let mut candidates = library.files.iter().flat_map(|file| file.structs.iter().filter(is_target));
+    match (candidates.next(), candidates.next()) {
+        (Some(struct_item), None) => struct_item.is_public,
+        _ => true,
+    }
+}
+
+fn lint_params(path: &Path, function: &NoirFunction, diagnostics: &mut Vec<Diagnostic>) {
+    let doc_comment = match &function.doc_comment {
+        Some(doc_comment) => doc_comment,
+        None => return,
+    };
+
+    let (_, params) = parse_doc_comment(doc_comment);
+    for (name, _, _) in params {
+        if !function.params.iter().any(|param| param.name == name) {
+            let known: Vec<&str> = function.params.iter().map(|param| param.name.as_str()).collect();
+            diagnostics.push(
+                Diagnostic::warning("unknown-param", format!("`@param {}` does not match a parameter of `{}`", name, function.name))
+                    .at(path, function.span)
+                    .with_help(if known.is_empty() {
+                        format!("`{}` takes no parameters", function.name)
+                    } else {
+                        format!("parameters of `{}` are: {}", function.name, known.join(", "))
+                    }),
+            );
+        }
+    }
+}
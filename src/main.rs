@@ -1,118 +1,277 @@
-use std::fs;
-use std::path::PathBuf;
-use tempfile::TempDir;
-
-mod parser;
-mod generator;
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Create a temporary directory
-    let temp_dir = TempDir::new()?;
-    let input_dir = temp_dir.path();
-
-    // Create a test Noir-like file in the temporary directory
-    let test_file_content = r#"
-    // typedoc: true
-    use dep::aztec::context::{PrivateContext, PublicContext};
-use dep::aztec::protocol_types::{address::AztecAddress, abis::function_selector::FunctionSelector, hash::pedersen_hash};
-
-use crate::entrypoint::{app::AppPayload, fee::FeePayload};
-use crate::auth::{IS_VALID_SELECTOR, compute_authwit_message_hash};
-
-struct AccountActions<Context> {
-  context: Context,
-  is_valid_impl: fn(&mut PrivateContext, Field) -> bool,
+use clap::{Args, Parser, Subcommand};
+use noir_docs_generator::diagnostics::{self, MessageFormat, Severity};
+use noir_docs_generator::{config, render, Diagnostic, DocBuilder, Documentation, Error, OrderStrategy};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+mod serve;
+
+// The command finished with nothing to report, or with warnings outside `check`
+const EXIT_SUCCESS: u8 = 0;
+// The command ran, but some files could not be read, parsed or rendered, or the output not written
+const EXIT_ERRORS: u8 = 1;
+// The command line or configuration is invalid, so nothing was done; clap uses it for bad flags too
+const EXIT_USAGE: u8 = 2;
+// `check` found documentation warnings but no errors
+const EXIT_WARNINGS: u8 = 3;
+
+const EXIT_CODES_HELP: &str = "Exit codes:
+  0  success
+  1  some files could not be read, parsed or rendered, or the output could not be written
+  2  invalid command line or configuration
+  3  `check` found documentation warnings";
+
+#[derive(Parser)]
+#[command(name = "noirdoc", version, about = "Generate API documentation for Noir crates", after_help = EXIT_CODES_HELP)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Diagnostic output: human, json (one object per line on stdout) or sarif (a SARIF 2.1.0 log on stdout)
+    #[arg(long, global = true, default_value = "human")]
+    message_format: MessageFormat,
 }
 
-impl<Context> AccountActions<Context> {
-    pub fn init(context: Context, is_valid_impl: fn(&mut PrivateContext, Field) -> bool) -> Self {
-        AccountActions { context, is_valid_impl }
-    }
+#[derive(Subcommand)]
+enum Command {
+    /// Generate documentation for the Noir sources under <PATH>
+    Build {
+        #[command(flatten)]
+        source: SourceArgs,
+        /// Directory the documentation is written to (overrides `output.dir`)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Parse and render everything without writing any output
+    Check {
+        #[command(flatten)]
+        source: SourceArgs,
+    },
+    /// Remove previously generated documentation
+    Clean {
+        /// Directory containing the Noir crate(s) the documentation was generated for
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Configuration file, defaults to `<PATH>/noirdoc.toml` when present
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Directory the documentation was written to (overrides `output.dir`)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Output format the documentation was generated in (overrides `output.format`)
+        #[arg(long)]
+        format: Option<render::OutputFormat>,
+    },
+    /// Build the documentation and serve the output directory over HTTP
+    Serve {
+        #[command(flatten)]
+        source: SourceArgs,
+        /// Directory the documentation is written to (overrides `output.dir`)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Port to listen on
+        #[arg(long, default_value_t = 3000)]
+        port: u16,
+    },
 }
 
-/**
- * An implementation of the Account Action struct for the private context.
- * 
- * Implements logic to verify authorization and execute payloads.
- */
-impl AccountActions<&mut PrivateContext> {
-
-    /** 
-     * Verifies that the `app_hash` and `fee_hash` are authorized and then executes them.
-     * 
-     * Executes the `fee_payload` and `app_payload` in sequence.
-     * Will execute the `fee_payload` as part of the setup, and then enter the app phase.
-     * 
-     * @param app_payload The payload that contains the calls to be executed in the app phase.
-     * @param fee_payload The payload that contains the calls to be executed in the setup phase.
-     */
-    // docs:start:entrypoint
-    pub fn entrypoint(self, app_payload: AppPayload, fee_payload: FeePayload) {
-        let valid_fn = self.is_valid_impl;
-
-        let fee_hash = fee_payload.hash();
-        assert(valid_fn(self.context, fee_hash));
-        fee_payload.execute_calls(self.context);
-        self.context.end_setup();
-
-        let app_hash = app_payload.hash();
-        assert(valid_fn(self.context, app_hash));
-        app_payload.execute_calls(self.context);
-    }
-    // docs:end:entrypoint
-
-    /**
-     * Verifies that the `msg_sender` is authorized to consume `inner_hash` by the account.
-     * 
-     * Computes the `message_hash` using the `msg_sender`, `chain_id`, `version` and `inner_hash`.
-     * Then executes the `is_valid_impl` function to verify that the message is authorized.
-     * 
-     * Will revert if the message is not authorized. 
-     * 
-     * @param inner_hash The hash of the message that the `msg_sender` is trying to consume.
-     */
-    // docs:start:verify_private_authwit
-    pub fn verify_private_authwit(self, inner_hash: Field) -> Field {
-        // The `inner_hash` is "siloed" with the `msg_sender` to ensure that only it can 
-        // consume the message.
-        // This ensures that contracts cannot consume messages that are not intended for them.
-        let message_hash = compute_authwit_message_hash(
-            self.context.msg_sender(),
-            self.context.chain_id(),
-            self.context.version(),
-            inner_hash
-        );
-        let valid_fn = self.is_valid_impl;
-        assert(valid_fn(self.context, message_hash) == true, "Message not authorized by account");
-        IS_VALID_SELECTOR
+#[derive(Args)]
+struct SourceArgs {
+    /// Directory containing the Noir crate(s) to document
+    #[arg(default_value = ".")]
+    path: PathBuf,
+    /// Configuration file, defaults to `<PATH>/noirdoc.toml` when present
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Glob selecting source files, relative to PATH (repeatable, replaces `source.include`)
+    #[arg(long)]
+    include: Vec<String>,
+    /// Glob excluding source files or directories, relative to PATH (repeatable, added to `source.exclude`)
+    #[arg(long)]
+    exclude: Vec<String>,
+    /// Ordering of pages and items: source, alphabetical or kind-then-name (overrides `source.order`)
+    #[arg(long)]
+    order: Option<OrderStrategy>,
+    /// Output format: docusaurus, html, json, llms-txt or mdbook (overrides `output.format`)
+    #[arg(long)]
+    format: Option<render::OutputFormat>,
+    /// Render a documentation model exported with `--format json` instead of parsing PATH (repeatable)
+    #[arg(long = "model", value_name = "MODEL_JSON")]
+    models: Vec<PathBuf>,
+    /// Link to an externally documented crate, as <BASE_URL>=<INVENTORY_JSON> (repeatable)
+    #[arg(long = "extern", value_name = "BASE_URL=INVENTORY")]
+    externs: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let is_check = matches!(cli.command, Command::Check { .. });
+
+    let (diagnostics, exit_code) = match run(cli.command, cli.message_format) {
+        Ok(diagnostics) => {
+            let exit_code = if diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error) {
+                EXIT_ERRORS
+            } else if is_check && !diagnostics.is_empty() {
+                EXIT_WARNINGS
+            } else {
+                EXIT_SUCCESS
+            };
+            (diagnostics, exit_code)
+        }
+        Err(e @ Error::Config { .. }) => (vec![Diagnostic::from(&e)], EXIT_USAGE),
+        Err(e) => (vec![Diagnostic::from(&e)], EXIT_ERRORS),
+    };
+
+    report_diagnostics(&diagnostics, cli.message_format);
+    ExitCode::from(exit_code)
+}
+
+// Returns the problems that did not stop the command, fatal errors abort it
+fn run(command: Command, format: MessageFormat) -> Result<Vec<Diagnostic>, Error> {
+    // Machine-readable diagnostics own stdout, so progress goes to stderr
+    let status = |message: String| match format {
+        MessageFormat::Human => println!("{}", message),
+        _ => eprintln!("{}", message),
+    };
+
+    match command {
+        Command::Build { source, out } => {
+            let builder = doc_builder(&source, out)?;
+            let documentation = builder.build()?;
+            documentation.write(builder.config())?;
+            status(format!("Generated {} files in '{}'", documentation.files.len(), builder.config().output.dir.display()));
+            Ok(collect_diagnostics(documentation))
+        }
+        Command::Check { source } => {
+            let documentation = doc_builder(&source, None)?.build()?;
+            let file_count: usize = documentation.libraries.iter().map(|library| library.files.len()).sum();
+            let item_count: usize = documentation.inventories.iter().map(|inventory| inventory.items.len()).sum();
+            status(format!("Checked {} crates: {} files, {} items", documentation.libraries.len(), file_count, item_count));
+            Ok(collect_diagnostics(documentation))
+        }
+        Command::Clean { path, config, out, format } => {
+            let mut config = read_config(&path, config.as_deref())?;
+            if let Some(out) = out {
+                config.output.dir = out;
+            }
+            if let Some(format) = format {
+                config.output.format = format;
+            }
+            render::clean_output(render::renderer(config.output.format).as_ref(), &config)?;
+            status(format!("Removed generated documentation from '{}'", config.output.dir.display()));
+            Ok(Vec::new())
+        }
+        Command::Serve { source, out, port } => {
+            let builder = doc_builder(&source, out)?;
+            let documentation = builder.build()?;
+            documentation.write(builder.config())?;
+            // Serving only ends on an error, so the build's diagnostics are reported first
+            report_diagnostics(&collect_diagnostics(documentation), format);
+            let output_dir = &builder.config().output.dir;
+            let listener = serve::bind(port).map_err(|e| Error::io(output_dir, e))?;
+            status(format!("Serving '{}' at http://127.0.0.1:{}/ (Ctrl+C to stop)", output_dir.display(), port));
+            serve::serve(output_dir, listener).map_err(|e| Error::io(output_dir, e))?;
+            Ok(Vec::new())
+        }
     }
-    // docs:end:verify_private_authwit
 }
 
-    "#;
+fn report_diagnostics(diagnostics: &[Diagnostic], format: MessageFormat) {
+    match format {
+        MessageFormat::Human => {
+            eprint!("{}", diagnostics::format_diagnostics(diagnostics, MessageFormat::Human));
+            if !diagnostics.is_empty() {
+                eprintln!("\n{}", diagnostic_summary(diagnostics));
+            }
+        }
+        format => print!("{}", diagnostics::format_diagnostics(diagnostics, format)),
+    }
+}
 
-    let test_file_path = input_dir.join("test_noir_file.nr");
-    fs::write(&test_file_path, test_file_content)?;
+// Errors first, then lint warnings
+fn collect_diagnostics(documentation: Documentation) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = documentation.errors.iter().map(Diagnostic::from).collect();
+    diagnostics.extend(documentation.warnings);
+    diagnostics
+}
 
-    // Parse the test file
-    match parser::parse_noir_file(test_file_path.to_str().unwrap()) {
-        Ok(noir_file) => {
-            println!("Parsed Noir file: {}", noir_file.name);
-            println!("Parsed Noir file: {:?}", noir_file);
+// e.g. "2 errors, 3 warnings (2 parse-error, 3 missing-docs)"
+fn diagnostic_summary(diagnostics: &[Diagnostic]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for diagnostic in diagnostics {
+        match counts.iter_mut().find(|(code, _)| *code == diagnostic.code) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((diagnostic.code, 1)),
+        }
+    }
 
-            // Generate Docusaurus docs
-            let (docs, sidebar) = generator::generate_docusaurus_docs(input_dir.to_str().unwrap());
+    let plural = |count: usize, noun: &str| format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" });
+    let error_count = diagnostics.iter().filter(|diagnostic| diagnostic.severity == Severity::Error).count();
+    let counts: Vec<String> = counts.iter().map(|(code, count)| format!("{} {}", count, code)).collect();
+    format!(
+        "{}, {} ({})",
+        plural(error_count, "error"),
+        plural(diagnostics.len() - error_count, "warning"),
+        counts.join(", ")
+    )
+}
 
-            // Write the generated docs and sidebar
-            let output_dir = PathBuf::from("docusaurus_output");
-            generator::write_docusaurus_docs(docs, sidebar, output_dir.to_str().unwrap())?;
+// PATH is only parsed when no models are given; it still locates `noirdoc.toml`
+fn doc_builder(source: &SourceArgs, out: Option<PathBuf>) -> Result<DocBuilder, Error> {
+    let builder = DocBuilder::new(load_config(source, out)?);
+    if source.models.is_empty() {
+        return Ok(builder.input(&source.path));
+    }
+    Ok(source.models.iter().fold(builder, |builder, model| builder.model(model)))
+}
 
-            println!("Docusaurus documentation generated in '{}'", output_dir.display());
+fn read_config(path: &Path, config_path: Option<&Path>) -> Result<config::Config, Error> {
+    match config_path {
+        Some(config_path) => config::Config::load(config_path),
+        None => {
+            let default_path = path.join(config::CONFIG_FILE_NAME);
+            if default_path.is_file() {
+                config::Config::load(&default_path)
+            } else {
+                Ok(config::Config::default())
+            }
         }
-        Err(e) => println!("Error parsing file: {}", e),
     }
+}
 
-    // The temporary directory and its contents will be automatically deleted when temp_dir goes out of scope
+// Command-line flags take precedence over the configuration file
+fn load_config(source: &SourceArgs, out: Option<PathBuf>) -> Result<config::Config, Error> {
+    let mut config = read_config(&source.path, source.config.as_deref())?;
 
-    Ok(())
-}
\ No newline at end of file
+    if let Some(out) = out {
+        config.output.dir = out;
+    }
+    if !source.include.is_empty() {
+        config.source.include = source.include.clone();
+    }
+    config.source.exclude.extend(source.exclude.iter().cloned());
+    if let Some(order) = source.order {
+        config.source.order = order;
+    }
+    if let Some(format) = source.format {
+        config.output.format = format;
+    }
+    for pair in &source.externs {
+        match pair.split_once('=') {
+            Some((base_url, path)) => config.externs.push(config::ExternConfig {
+                base_url: base_url.to_string(),
+                inventory: PathBuf::from(path),
+            }),
+            None => {
+                return Err(Error::Config {
+                    path: None,
+                    key: None,
+                    span: None,
+                    message: format!("expected --extern <BASE_URL>=<INVENTORY_JSON>, got '{}'", pair),
+                })
+            }
+        }
+    }
+
+    config.validate()?;
+    Ok(config)
+}
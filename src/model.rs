@@ -0,0 +1,244 @@
+use crate::error::Error;
+use crate::generator::{constant_signature, doc_summary, function_signature, parse_doc_comment, qualified_module_name, type_alias_signature, Library, LibraryPages, Site};
+use crate::inventory::{self, LinkResolver, LinkTarget};
+use crate::parser::{NoirFile, Span};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) const MODEL_VERSION: u32 = 3;
+pub(crate) const MODEL_FILE: &str = "model.json";
+pub(crate) const MODEL_SCHEMA_FILE: &str = "model.schema.json";
+// JSON Schema of the model, also published as `schema/model.schema.json`
+pub(crate) const MODEL_SCHEMA: &str = include_str!("../schema/model.schema.json");
+
+// The full documentation model as exported to JSON. Each module carries the parsed file as-is
+// plus a flat list of its items with what the generator derives from them: qualified paths,
+// anchors, signatures, doc sections and resolved links.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct DocModel {
+    pub format_version: u32,
+    #[serde(default)]
+    pub generator: String,
+    pub crates: Vec<CrateModel>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CrateModel {
+    pub name: String,
+    // Input directory the crate was found in; module file paths are relative to it
+    pub root: PathBuf,
+    // Browsable URL of `root`, so source links keep pointing to the crate's own repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository_url: Option<String>,
+    #[serde(default)]
+    pub index_page: String,
+    pub modules: Vec<ModuleModel>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ModuleModel {
+    // e.g. `aztec::context::private_context`
+    #[serde(default)]
+    pub qualified_name: String,
+    // Doc ID of the module's page
+    #[serde(default)]
+    pub page: String,
+    #[serde(flatten)]
+    pub file: NoirFile,
+    #[serde(default)]
+    pub items: Vec<ItemModel>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ItemModel {
+    pub path: String,
+    // One of the inventory kinds: struct, trait, trait_method, type_alias, function, constant, method
+    pub kind: String,
+    // Doc ID of the page the item is documented on
+    #[serde(default)]
+    pub page: String,
+    // Heading ID on that page, absent when the item has a page of its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs: Option<DocSections>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<DocLink>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct DocSections {
+    pub summary: String,
+    pub description: String,
+    #[serde(default)]
+    pub params: Vec<ParamDoc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ParamDoc {
+    pub name: String,
+    pub description: String,
+}
+
+// An intra-doc link; `target` is the full path of the item it resolved to, and `url` is set
+// when that item is documented elsewhere
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct DocLink {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+pub(crate) fn build_model(site: &Site, resolver: &LinkResolver) -> DocModel {
+    let crates = site
+        .libraries
+        .iter()
+        .map(|pages| CrateModel {
+            name: pages.library.name.clone(),
+            root: pages.library.root.clone(),
+            repository_url: pages.library.repository_url.clone(),
+            index_page: pages.index_id.clone(),
+            modules: pages
+                .module_pages
+                .iter()
+                .enumerate()
+                .map(|(module, (page, file))| {
+                    let qualified_name = qualified_module_name(&pages.library.name, file);
+                    ModuleModel {
+                        items: module_items(pages, module, resolver),
+                        qualified_name,
+                        page: page.clone(),
+                        file: NoirFile::clone(file),
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    DocModel {
+        format_version: MODEL_VERSION,
+        generator: format!("noirdoc {}", env!("CARGO_PKG_VERSION")),
+        crates,
+    }
+}
+
+fn module_items(pages: &LibraryPages, module: usize, resolver: &LinkResolver) -> Vec<ItemModel> {
+    let file = pages.module_pages[module].1;
+    let module_name = qualified_module_name(&pages.library.name, file);
+    let mut items = Vec::new();
+    let item = |name: &str, kind: &str, (page, anchor): (String, Option<String>), span: Span, doc_comment: Option<&str>, signature: Option<String>| ItemModel {
+        path: format!("{}::{}", module_name, name),
+        kind: kind.to_string(),
+        page,
+        anchor,
+        span,
+        signature,
+        docs: doc_comment.map(doc_sections),
+        links: doc_comment.map(|doc_comment| doc_links(doc_comment, &pages.library.name, resolver)).unwrap_or_default(),
+    };
+
+    for struct_item in &file.structs {
+        let location = pages.item_location(module, &inventory::item_anchor("struct", &[&struct_item.name]));
+        items.push(item(&struct_item.name, "struct", location, struct_item.span, struct_item.doc_comment.as_deref(), None));
+    }
+    for trait_item in &file.traits {
+        let (page, anchor) = pages.item_location(module, &inventory::item_anchor("trait", &[&trait_item.name]));
+        items.push(item(&trait_item.name, "trait", (page.clone(), anchor), trait_item.span, trait_item.doc_comment.as_deref(), None));
+        for method in &trait_item.methods {
+            let name = format!("{}::{}", trait_item.name, method.name);
+            let anchor = inventory::item_anchor("tymethod", &[&trait_item.name, &method.name]);
+            items.push(item(&name, "trait_method", (page.clone(), Some(anchor)), method.span, method.doc_comment.as_deref(), Some(function_signature(method))));
+        }
+    }
+    for type_alias in &file.type_aliases {
+        let location = pages.item_location(module, &inventory::item_anchor("type", &[&type_alias.name]));
+        items.push(item(&type_alias.name, "type_alias", location, type_alias.span, type_alias.doc_comment.as_deref(), Some(type_alias_signature(type_alias))));
+    }
+    for function in &file.functions {
+        let location = pages.item_location(module, &inventory::item_anchor("fn", &[&function.name]));
+        items.push(item(&function.name, "function", location, function.span, function.doc_comment.as_deref(), Some(function_signature(function))));
+    }
+    for constant in &file.constants {
+        let location = pages.item_location(module, &inventory::item_anchor("constant", &[&constant.name]));
+        items.push(item(&constant.name, "constant", location, constant.span, constant.doc_comment.as_deref(), Some(constant_signature(constant))));
+    }
+
+    // Methods are listed with the module of the struct they belong to
+    for impl_doc in pages.impl_docs.iter().filter(|impl_doc| impl_doc.owner.map_or(impl_doc.module, |(owner, _)| owner) == module) {
+        let page = pages.impl_page(impl_doc);
+        let target_path = inventory::method_parent_path(pages, impl_doc);
+        let target = target_path.strip_prefix(&format!("{}::", module_name)).unwrap_or(&target_path);
+        for (method, anchor) in impl_doc.impl_item.methods.iter().zip(&impl_doc.method_anchors) {
+            let name = format!("{}::{}", target, method.name);
+            items.push(item(&name, "method", (page.clone(), Some(anchor.clone())), method.span, method.doc_comment.as_deref(), Some(function_signature(method))));
+        }
+    }
+
+    items
+}
+
+fn doc_sections(doc_comment: &str) -> DocSections {
+    let (description, params) = parse_doc_comment(doc_comment);
+    DocSections {
+        summary: doc_summary(doc_comment),
+        description,
+        params: params.into_iter().map(|(name, _, description)| ParamDoc { name, description }).collect(),
+    }
+}
+
+fn doc_links(doc_comment: &str, crate_name: &str, resolver: &LinkResolver) -> Vec<DocLink> {
+    inventory::intra_doc_links(doc_comment)
+        .into_iter()
+        .map(|text| DocLink {
+            target: resolver.canonical_path(&text, crate_name),
+            url: match resolver.resolve(&text, crate_name) {
+                Some(LinkTarget::External(url)) => Some(url.clone()),
+                _ => None,
+            },
+            text,
+        })
+        .collect()
+}
+
+// Load a model written by a previous run, e.g. to render several repositories into one site.
+// Only the parsed files are read back; everything derived from them is recomputed.
+pub(crate) fn load_model(path: &Path) -> Result<Vec<Library>, Error> {
+    let content = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    let doc_model: DocModel = serde_json::from_str(&content).map_err(|e| Error::Parse {
+        path: path.to_path_buf(),
+        message: format!("invalid documentation model: {}", e),
+        span: Span {
+            line: e.line(),
+            column: e.column(),
+            length: 1,
+        },
+    })?;
+    if doc_model.format_version != MODEL_VERSION {
+        return Err(Error::Parse {
+            path: path.to_path_buf(),
+            message: format!("unsupported model version {}, expected {}", doc_model.format_version, MODEL_VERSION),
+            span: Span {
+                line: 1,
+                column: 1,
+                length: 1,
+            },
+        });
+    }
+
+    Ok(doc_model
+        .crates
+        .into_iter()
+        .map(|crate_model| Library {
+            name: crate_model.name,
+            root: crate_model.root,
+            repository_url: crate_model.repository_url,
+            files: crate_model.modules.into_iter().map(|module| module.file).collect(),
+        })
+        .collect())
+}
@@ -0,0 +1,81 @@
+use crate::inventory::{base_type_path, InventoryItem};
+use crate::parser::NoirFile;
+use serde::Deserialize;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+/// Order of files and of the items within them.
+pub enum OrderStrategy {
+    /// Items keep the order they appear in, modules follow their paths with parents first.
+    #[default]
+    Source,
+    /// Items are sorted by name, whatever their kind.
+    Alphabetical,
+    /// Items are grouped by kind and sorted by name within each kind.
+    #[serde(alias = "kind")]
+    KindThenName,
+}
+
+impl FromStr for OrderStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "source" => Ok(OrderStrategy::Source),
+            "alphabetical" => Ok(OrderStrategy::Alphabetical),
+            "kind" | "kind-then-name" => Ok(OrderStrategy::KindThenName),
+            _ => Err(format!("unknown ordering '{}', expected 'source', 'alphabetical' or 'kind-then-name'", s)),
+        }
+    }
+}
+
+impl OrderStrategy {
+    pub(crate) fn sorts_by_name(&self) -> bool {
+        *self != OrderStrategy::Source
+    }
+}
+
+pub(crate) fn sort_file_items(file: &mut NoirFile, strategy: OrderStrategy) {
+    if !strategy.sorts_by_name() {
+        return;
+    }
+
+    // Alphabetical order interleaves the kinds later, in `generator::file_items`
+    file.structs.sort_by(|a, b| a.name.cmp(&b.name));
+    file.traits.sort_by(|a, b| a.name.cmp(&b.name));
+    for trait_item in &mut file.traits {
+        trait_item.methods.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    file.type_aliases.sort_by(|a, b| a.name.cmp(&b.name));
+    file.functions.sort_by(|a, b| a.name.cmp(&b.name));
+    file.constants.sort_by(|a, b| a.name.cmp(&b.name));
+    // Stable sort keeps several impl blocks for the same type in source order
+    file.impls.sort_by_key(|impl_item| base_type_path(&impl_item.target));
+    for impl_item in &mut file.impls {
+        impl_item.methods.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+// Position of an inventory kind in the order module pages list items
+pub(crate) fn kind_rank(kind: &str) -> usize {
+    match kind {
+        "module" => 0,
+        "struct" => 1,
+        "trait" => 2,
+        "trait_method" => 3,
+        "type_alias" => 4,
+        "function" => 5,
+        "constant" => 6,
+        "method" => 7,
+        _ => 8,
+    }
+}
+
+pub(crate) fn sort_inventory_items(items: &mut [InventoryItem], strategy: OrderStrategy) {
+    match strategy {
+        OrderStrategy::Source => {}
+        OrderStrategy::Alphabetical => items.sort_by(|a, b| a.path.cmp(&b.path)),
+        OrderStrategy::KindThenName => items.sort_by(|a, b| (kind_rank(&a.kind), &a.path).cmp(&(kind_rank(&b.kind), &b.path))),
+    }
+}
@@ -1,71 +1,220 @@
-use syn::{parse_file, Item, ItemFn, ItemStruct, ItemTrait, ItemImpl};
-use syn::{Fields, FieldsNamed, Type, Pat, FnArg, ReturnType, Attribute};
+use syn::{parse_file, Item, ItemConst, ItemFn, ItemStruct, ItemTrait, ItemImpl, ItemType};
+use syn::{Fields, FieldsNamed, Type, Pat, FnArg, ReturnType, Attribute, Ident};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use quote::ToTokens;
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+/// A parsed source file, documented as one module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoirFile {
+    /// File name without extension.
     pub name: String,
+    /// Path as read, made relative to the input directory by the generator.
+    pub path: PathBuf,
+    /// Module segments within the crate, filled in by the generator (empty for the crate root).
+    pub module_path: Vec<String>,
+    /// Inner `//!` documentation of the module.
+    #[serde(default)]
+    pub doc_comment: Option<String>,
+    /// Structs, in source order.
     pub structs: Vec<NoirStruct>,
+    /// Traits, in source order.
     pub traits: Vec<NoirTrait>,
+    /// Free functions, in source order.
     pub functions: Vec<NoirFunction>,
+    /// Impl blocks, in source order.
     pub impls: Vec<NoirImpl>,
+    /// Constants, in source order.
+    #[serde(default)]
+    pub constants: Vec<NoirConstant>,
+    /// Type aliases, in source order.
+    #[serde(default)]
+    pub type_aliases: Vec<NoirTypeAlias>,
+}
+
+/// Location of an item's name in its source file; line and column are 1-based.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    /// Line number, from 1.
+    pub line: usize,
+    /// Column in characters, from 1.
+    pub column: usize,
+    /// Length in characters.
+    pub length: usize,
 }
 
-#[derive(Debug)]
+/// A struct declaration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoirStruct {
+    /// The struct's name.
     pub name: String,
+    /// Generic parameters, e.g. `K` or `let N: u32`.
+    #[serde(default)]
+    pub generic_params: Vec<String>,
+    /// Named fields, in declaration order.
     pub fields: Vec<NoirField>,
+    /// Outer `///` documentation.
+    pub doc_comment: Option<String>,
+    /// Traits listed in `#[derive(...)]`, as written.
+    #[serde(default)]
+    pub derives: Vec<String>,
+    /// Whether the struct is `pub`.
+    #[serde(default)]
+    pub is_public: bool,
+    /// Location of the name.
+    pub span: Span,
 }
 
-#[derive(Debug)]
+/// A named struct field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoirField {
+    /// The field's name.
     pub name: String,
+    /// Its type, as tokens separated by spaces, e.g. `BoundedVec < Field , 4 >`.
     pub ty: String,
+    /// Whether the field is `pub`.
+    #[serde(default)]
+    pub is_public: bool,
 }
 
-#[derive(Debug)]
+/// A trait declaration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoirTrait {
+    /// The trait's name.
     pub name: String,
+    /// Method declarations, with or without default bodies.
     pub methods: Vec<NoirFunction>,
+    /// Outer `///` documentation.
+    pub doc_comment: Option<String>,
+    /// Whether the trait is `pub`.
+    #[serde(default)]
+    pub is_public: bool,
+    /// Location of the name.
+    pub span: Span,
 }
 
-#[derive(Debug)]
+/// A free function, trait method or impl method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoirFunction {
+    /// The function's name.
     pub name: String,
+    /// The `self` receiver as written, e.g. `&mut self`; `None` for functions that take none.
+    #[serde(default)]
+    pub receiver: Option<String>,
+    /// Parameters other than `self`.
     pub params: Vec<NoirParam>,
+    /// The return type, if the function returns a value.
     pub return_type: Option<String>,
+    /// Outer `///` documentation.
     pub doc_comment: Option<String>,
+    /// Attributes other than doc comments, e.g. `#[test]`.
     pub attributes: Vec<String>,
+    /// Generic parameters, e.g. `T` or `let N: u32`.
     pub generic_params: Vec<String>,
+    /// Whether the function is `unconstrained`.
     pub is_unconstrained: bool,
+    /// Whether the function is `pub`; trait methods always are.
+    pub is_public: bool,
+    /// Location of the name.
+    pub span: Span,
 }
 
-#[derive(Debug)]
+/// A function parameter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoirParam {
+    /// The parameter's pattern, usually its name.
     pub name: String,
+    /// Its type.
     pub ty: String,
 }
 
-#[derive(Debug)]
+/// A global constant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoirConstant {
+    /// The constant's name.
+    pub name: String,
+    /// Its type.
+    pub ty: String,
+    /// Its value expression.
+    pub value: String,
+    /// Outer `///` documentation.
+    pub doc_comment: Option<String>,
+    /// Whether the constant is `pub`.
+    pub is_public: bool,
+    /// Location of the name.
+    pub span: Span,
+}
+
+/// A type alias, e.g. `type Context = PrivateContext;`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoirTypeAlias {
+    /// The alias' name.
+    pub name: String,
+    /// Generic parameters.
+    #[serde(default)]
+    pub generic_params: Vec<String>,
+    /// The aliased type.
+    pub ty: String,
+    /// Outer `///` documentation.
+    pub doc_comment: Option<String>,
+    /// Whether the alias is `pub`.
+    pub is_public: bool,
+    /// Location of the name.
+    pub span: Span,
+}
+
+/// An impl block, of a trait or inherent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoirImpl {
+    /// The implementing type, e.g. `AccountActions < Context >`.
     pub target: String,
+    /// The implemented trait, e.g. `Serialize < 3 >`; `None` for inherent impls.
+    #[serde(default)]
+    pub trait_name: Option<String>,
+    /// Generic parameters of the block.
+    #[serde(default)]
+    pub generic_params: Vec<String>,
+    /// Outer `///` documentation of the block.
+    #[serde(default)]
+    pub doc_comment: Option<String>,
+    /// Methods, in source order.
     pub methods: Vec<NoirFunction>,
+    /// Location of the `impl` keyword.
+    pub span: Span,
 }
 
-pub fn parse_noir_file(file_path: &str) -> Result<NoirFile, Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(file_path)?;
-    let ast = parse_file(&content)?;
+pub(crate) fn parse_noir_file(file_path: &Path) -> Result<NoirFile, Error> {
+    let content = fs::read_to_string(file_path).map_err(|e| Error::io(file_path, e))?;
+    let ast = parse_file(&content).map_err(|e| {
+        let start = e.span().start();
+        Error::Parse {
+            path: file_path.to_path_buf(),
+            message: e.to_string(),
+            span: Span {
+                line: start.line,
+                column: start.column + 1,
+                length: 1,
+            },
+        }
+    })?;
 
-    let file_name = Path::new(file_path).file_stem().unwrap().to_str().unwrap().to_string();
+    let file_name = file_path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
 
     let mut noir_file = NoirFile {
         name: file_name,
+        path: file_path.to_path_buf(),
+        module_path: Vec::new(),
+        doc_comment: extract_doc_comment(&ast.attrs),
         structs: Vec::new(),
         traits: Vec::new(),
         functions: Vec::new(),
         impls: Vec::new(),
+        constants: Vec::new(),
+        type_aliases: Vec::new(),
     };
 
     for item in ast.items {
@@ -74,6 +223,9 @@ pub fn parse_noir_file(file_path: &str) -> Result<NoirFile, Box<dyn std::error::
             Item::Trait(t) => noir_file.traits.push(parse_trait(t)),
             Item::Fn(f) => noir_file.functions.push(parse_function(f)),
             Item::Impl(i) => noir_file.impls.push(parse_impl(i)),
+            Item::Const(c) => noir_file.constants.push(parse_constant(c)),
+            Item::Type(t) => noir_file.type_aliases.push(parse_type_alias(t)),
+            // `use` declarations and inline modules are not documented
             _ => {}
         }
     }
@@ -83,21 +235,29 @@ pub fn parse_noir_file(file_path: &str) -> Result<NoirFile, Box<dyn std::error::
 
 fn parse_struct(s: ItemStruct) -> NoirStruct {
     let name = s.ident.to_string();
+    let doc_comment = extract_doc_comment(&s.attrs);
+    let derives = extract_derives(&s.attrs);
+    let span = ident_span(&s.ident);
     let fields = match s.fields {
         Fields::Named(FieldsNamed { named, .. }) => named
             .into_iter()
             .map(|f| NoirField {
+                is_public: matches!(f.vis, syn::Visibility::Public(_)),
                 name: f.ident.unwrap().to_string(),
                 ty: type_to_string(&f.ty),
             })
             .collect(),
         _ => Vec::new(), // Handle unnamed fields if needed
     };
-    NoirStruct { name, fields }
+    let generic_params = s.generics.params.iter().map(|param| param.to_token_stream().to_string()).collect();
+    let is_public = matches!(s.vis, syn::Visibility::Public(_));
+    NoirStruct { name, generic_params, fields, doc_comment, derives, is_public, span }
 }
 
 fn parse_trait(t: ItemTrait) -> NoirTrait {
     let name = t.ident.to_string();
+    let doc_comment = extract_doc_comment(&t.attrs);
+    let span = ident_span(&t.ident);
     let methods = t.items
         .into_iter()
         .filter_map(|item| {
@@ -108,11 +268,14 @@ fn parse_trait(t: ItemTrait) -> NoirTrait {
             }
         })
         .collect();
-    NoirTrait { name, methods }
+    let is_public = matches!(t.vis, syn::Visibility::Public(_));
+    NoirTrait { name, methods, doc_comment, is_public, span }
 }
 
 fn parse_function(f: ItemFn) -> NoirFunction {
     let name = f.sig.ident.to_string();
+    let span = ident_span(&f.sig.ident);
+    let receiver = receiver(&f.sig.inputs);
     let params = f.sig.inputs.iter().filter_map(|arg| {
         if let FnArg::Typed(pat_type) = arg {
             Some(NoirParam {
@@ -133,20 +296,33 @@ fn parse_function(f: ItemFn) -> NoirFunction {
     let attributes = extract_attributes(&f.attrs);
     let generic_params = f.sig.generics.params.iter().map(|param| param.to_token_stream().to_string()).collect();
     let is_unconstrained = f.sig.constness.is_some() || attributes.iter().any(|attr| attr.contains("unconstrained"));
+    let is_public = matches!(f.vis, syn::Visibility::Public(_));
 
     NoirFunction {
         name,
+        receiver,
         params,
         return_type,
         doc_comment,
         attributes,
         generic_params,
         is_unconstrained,
+        is_public,
+        span,
     }
 }
 
 fn parse_impl(i: ItemImpl) -> NoirImpl {
     let target = type_to_string(&i.self_ty);
+    let trait_name = i.trait_.as_ref().map(|(_, path, _)| path.to_token_stream().to_string());
+    let generic_params = i.generics.params.iter().map(|param| param.to_token_stream().to_string()).collect();
+    let doc_comment = extract_doc_comment(&i.attrs);
+    let start = i.impl_token.span.start();
+    let span = Span {
+        line: start.line,
+        column: start.column + 1,
+        length: "impl".len(),
+    };
     let methods = i.items
         .into_iter()
         .filter_map(|item| {
@@ -157,11 +333,42 @@ fn parse_impl(i: ItemImpl) -> NoirImpl {
             }
         })
         .collect();
-    NoirImpl { target, methods }
+    NoirImpl {
+        target,
+        trait_name,
+        generic_params,
+        doc_comment,
+        methods,
+        span,
+    }
+}
+
+fn parse_constant(c: ItemConst) -> NoirConstant {
+    NoirConstant {
+        name: c.ident.to_string(),
+        ty: type_to_string(&c.ty),
+        value: c.expr.to_token_stream().to_string(),
+        doc_comment: extract_doc_comment(&c.attrs),
+        is_public: matches!(c.vis, syn::Visibility::Public(_)),
+        span: ident_span(&c.ident),
+    }
+}
+
+fn parse_type_alias(t: ItemType) -> NoirTypeAlias {
+    NoirTypeAlias {
+        name: t.ident.to_string(),
+        generic_params: t.generics.params.iter().map(|param| param.to_token_stream().to_string()).collect(),
+        ty: type_to_string(&t.ty),
+        doc_comment: extract_doc_comment(&t.attrs),
+        is_public: matches!(t.vis, syn::Visibility::Public(_)),
+        span: ident_span(&t.ident),
+    }
 }
 
 fn parse_trait_method(method: syn::TraitItemMethod) -> NoirFunction {
     let name = method.sig.ident.to_string();
+    let span = ident_span(&method.sig.ident);
+    let receiver = receiver(&method.sig.inputs);
     let params = method.sig.inputs
         .into_iter()
         .filter_map(|arg| {
@@ -186,17 +393,23 @@ fn parse_trait_method(method: syn::TraitItemMethod) -> NoirFunction {
 
     NoirFunction {
         name,
+        receiver,
         params,
         return_type,
         doc_comment,
         attributes,
         generic_params,
         is_unconstrained,
+        // Trait methods are as visible as the trait itself
+        is_public: true,
+        span,
     }
 }
 
 fn parse_impl_method(method: syn::ImplItemMethod) -> NoirFunction {
     let name = method.sig.ident.to_string();
+    let span = ident_span(&method.sig.ident);
+    let receiver = receiver(&method.sig.inputs);
     let params = method.sig.inputs
         .into_iter()
         .filter_map(|arg| {
@@ -218,15 +431,40 @@ fn parse_impl_method(method: syn::ImplItemMethod) -> NoirFunction {
     let attributes = extract_attributes(&method.attrs);
     let generic_params = method.sig.generics.params.iter().map(|param| param.to_token_stream().to_string()).collect();
     let is_unconstrained = method.sig.constness.is_some() || attributes.iter().any(|attr| attr.contains("unconstrained"));
+    let is_public = matches!(method.vis, syn::Visibility::Public(_));
 
     NoirFunction {
         name,
+        receiver,
         params,
         return_type,
         doc_comment,
         attributes,
         generic_params,
         is_unconstrained,
+        is_public,
+        span,
+    }
+}
+
+// `self`, `mut self`, `&self` or `&mut self`
+fn receiver(inputs: &Punctuated<FnArg, Comma>) -> Option<String> {
+    inputs.iter().find_map(|arg| match arg {
+        FnArg::Receiver(receiver) => {
+            let reference = if receiver.reference.is_some() { "&" } else { "" };
+            let mutability = if receiver.mutability.is_some() { "mut " } else { "" };
+            Some(format!("{}{}self", reference, mutability))
+        }
+        FnArg::Typed(_) => None,
+    })
+}
+
+fn ident_span(ident: &Ident) -> Span {
+    let start = ident.span().start();
+    Span {
+        line: start.line,
+        column: start.column + 1,
+        length: ident.to_string().chars().count(),
     }
 }
 
@@ -239,7 +477,7 @@ fn pat_to_string(pat: &Pat) -> String {
 }
 
 fn extract_doc_comment(attrs: &[Attribute]) -> Option<String> {
-    attrs.iter()
+    let doc_comment = attrs.iter()
         .filter(|attr| attr.path.is_ident("doc"))
         .filter_map(|attr| attr.parse_meta().ok())
         .filter_map(|meta| {
@@ -254,8 +492,23 @@ fn extract_doc_comment(attrs: &[Attribute]) -> Option<String> {
             }
         })
         .collect::<Vec<String>>()
-        .join("\n")
-        .into()
+        .join("\n");
+
+    if doc_comment.trim().is_empty() { None } else { Some(doc_comment) }
+}
+
+fn extract_derives(attrs: &[Attribute]) -> Vec<String> {
+    attrs.iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| attr.parse_meta().ok())
+        .flat_map(|meta| {
+            if let syn::Meta::List(list) = meta {
+                list.nested.into_iter().map(|nested| nested.to_token_stream().to_string()).collect()
+            } else {
+                Vec::new()
+            }
+        })
+        .collect()
 }
 
 fn extract_attributes(attrs: &[Attribute]) -> Vec<String> {
@@ -0,0 +1,498 @@
+//! The Docusaurus backend.
+
+use crate::config::{Config, PageMode};
+use crate::error::Error;
+use crate::generator::{doc_summary, file_items, qualified_module_name, LibraryPages, Site};
+use crate::inventory::LinkResolver;
+use crate::render::markdown::{generate_file_doc, generate_item_doc, generate_library_overview, generate_main_overview, source_url};
+use crate::render::{sidebar_patch, OutputFile, Renderer};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::PathBuf;
+
+pub(crate) const CATEGORY_FILE: &str = "_category_.json";
+
+/// An entry of a Docusaurus sidebar.
+#[derive(Debug)]
+pub enum SidebarItem {
+    /// A collapsible group of entries.
+    Category {
+        /// Text shown in the sidebar.
+        label: String,
+        /// The page opened by clicking the label.
+        link: Option<CategoryLink>,
+        /// Whether the category can be collapsed.
+        collapsible: bool,
+        /// Whether it starts collapsed.
+        collapsed: bool,
+        /// The entries within it.
+        items: Vec<SidebarItem>,
+    },
+    /// A page.
+    Doc {
+        /// The page's doc ID.
+        id: String,
+        /// Text shown in the sidebar.
+        label: String,
+    },
+    /// An item on a page, e.g. `/docs/aztec/context/private_context#struct-PrivateContext`.
+    Link {
+        /// Text shown in the sidebar.
+        label: String,
+        /// The item's URL.
+        href: String,
+    },
+}
+
+/// The page shown when a category's label is clicked.
+#[derive(Debug)]
+pub enum CategoryLink {
+    /// A generated page, by doc ID.
+    Doc(String),
+    /// A generated index of the category, for module directories without a page of their own.
+    GeneratedIndex {
+        /// Title of the index page.
+        title: String,
+        /// URL of the index page.
+        slug: String,
+    },
+}
+
+/// How the sidebar is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SidebarFormat {
+    /// A `sidebars.js` file.
+    #[default]
+    Js,
+    /// A `sidebars.ts` file.
+    Ts,
+    /// `_category_.json` files for Docusaurus' autogenerated sidebars.
+    Autogenerated,
+}
+
+/// What the sidebar file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SidebarMode {
+    /// The whole file, with a single sidebar.
+    #[default]
+    Replace,
+    /// Only the sidebar's items, for a hand-written sidebars file to import.
+    Module,
+    /// The items of one sidebar or category in an existing file, leaving the rest as it is.
+    Patch,
+}
+
+// Page metadata, so Docusaurus does not have to guess IDs, titles and ordering
+#[derive(Debug)]
+pub(crate) struct Frontmatter {
+    pub id: String,
+    pub title: String,
+    pub sidebar_label: String,
+    pub sidebar_position: usize,
+    pub description: String,
+    pub slug: String,
+    pub tags: Vec<String>,
+    // `None` hides the edit link on pages without a browsable source file
+    pub custom_edit_url: Option<String>,
+}
+
+/// Markdown pages under `output.docs_dir` plus a `sidebars.js` for Docusaurus.
+pub struct DocusaurusRenderer;
+
+impl Renderer for DocusaurusRenderer {
+    fn page_extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn render(&self, site: &Site, config: &Config, resolver: &LinkResolver) -> Result<Vec<OutputFile>, Error> {
+        let mut docs = Vec::new();
+
+        // Generate main overview page
+        let frontmatter = Frontmatter {
+            sidebar_position: 1,
+            description: doc_summary(&config.site.intro),
+            tags: Vec::new(),
+            ..page_frontmatter(&site.overview_id, &config.site.title, &config.site.overview_label)
+        };
+        docs.push(OutputFile {
+            path: page_path(config, &site.overview_id),
+            content: format!("{}{}", frontmatter.render(), generate_main_overview(config, &site.overview_id, &site.libraries)),
+        });
+
+        let mut sidebar = vec![SidebarItem::Doc {
+            id: site.overview_id.clone(),
+            label: config.site.overview_label.clone(),
+        }];
+
+        // Generate docs for each library (crate)
+        for pages in &site.libraries {
+            docs.extend(generate_library_docs(pages, config, resolver));
+            sidebar.push(library_sidebar(pages, config));
+        }
+
+        let sidebar_file = config.sidebar_file();
+        match (config.output.sidebar_format, config.output.sidebar_mode) {
+            (SidebarFormat::Autogenerated, _) => docs.extend(generate_category_files(site, config)),
+            (format, SidebarMode::Replace) => docs.push(OutputFile {
+                content: generate_sidebar_file(&sidebar, &config.output.sidebar_id, format),
+                path: sidebar_file,
+            }),
+            (format, SidebarMode::Module) => docs.push(OutputFile {
+                content: generate_sidebar_module(&sidebar, format),
+                path: sidebar_file,
+            }),
+            (_, SidebarMode::Patch) => docs.push(OutputFile {
+                content: patch_sidebar_file(&sidebar, config)?,
+                path: sidebar_file,
+            }),
+        }
+
+        Ok(docs)
+    }
+
+    // A patched sidebars file belongs to the site, so only the patched items are taken out
+    fn clean(&self, config: &Config) -> Result<(), Error> {
+        if config.output.sidebar_mode != SidebarMode::Patch || !config.sidebar_path().is_file() {
+            return Ok(());
+        }
+        let path = config.sidebar_path();
+        fs::write(&path, patch_sidebar_file(&[], config)?).map_err(|e| Error::io(&path, e))
+    }
+}
+
+// The slug pins each page to its doc ID, which is also the page inventories link to
+fn page_frontmatter(doc_id: &str, title: &str, sidebar_label: &str) -> Frontmatter {
+    Frontmatter {
+        id: doc_id.rsplit('/').next().unwrap_or(doc_id).to_string(),
+        title: title.to_string(),
+        sidebar_label: sidebar_label.to_string(),
+        sidebar_position: 1,
+        description: String::new(),
+        slug: format!("/{}", doc_id),
+        tags: Vec::new(),
+        custom_edit_url: None,
+    }
+}
+
+impl Frontmatter {
+    pub fn render(&self) -> String {
+        let mut content = String::from("---\n");
+        content.push_str(&format!("id: {}\n", yaml_string(&self.id)));
+        content.push_str(&format!("title: {}\n", yaml_string(&self.title)));
+        content.push_str(&format!("sidebar_label: {}\n", yaml_string(&self.sidebar_label)));
+        content.push_str(&format!("sidebar_position: {}\n", self.sidebar_position));
+        if !self.description.is_empty() {
+            content.push_str(&format!("description: {}\n", yaml_string(&self.description)));
+        }
+        content.push_str(&format!("slug: {}\n", yaml_string(&self.slug)));
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|tag| yaml_string(tag)).collect();
+            content.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+        }
+        match &self.custom_edit_url {
+            Some(url) => content.push_str(&format!("custom_edit_url: {}\n", yaml_string(url))),
+            None => content.push_str("custom_edit_url: null\n"),
+        }
+        content.push_str("---\n\n");
+        content
+    }
+}
+
+// JSON strings are valid double-quoted YAML scalars
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).expect("strings serialize to JSON")
+}
+
+fn page_path(config: &Config, doc_id: &str) -> PathBuf {
+    config.output.docs_dir.join(format!("{}.md", doc_id))
+}
+
+fn generate_library_docs(pages: &LibraryPages, config: &Config, resolver: &LinkResolver) -> Vec<OutputFile> {
+    let mut docs = Vec::new();
+    let library = pages.library;
+
+    // Generate main library page, described by the crate root's `//!` docs when it has them
+    let root_doc = library.files.iter().find(|file| file.module_path.is_empty()).and_then(|file| file.doc_comment.as_deref());
+    let frontmatter = Frontmatter {
+        sidebar_position: 1,
+        description: root_doc.map(doc_summary).unwrap_or_else(|| format!("API reference for the {} crate", library.name)),
+        tags: vec![library.name.clone()],
+        ..page_frontmatter(&pages.index_id, &library.name, &format!("{} Overview", library.name))
+    };
+    docs.push(OutputFile {
+        path: page_path(config, &pages.index_id),
+        content: format!("{}{}", frontmatter.render(), generate_library_overview(library, &pages.index_id, &pages.module_pages)),
+    });
+
+    // Generate pages for each module
+    for (module, (doc_id, file)) in pages.module_pages.iter().enumerate() {
+        let module_name = qualified_module_name(&library.name, file);
+        let frontmatter = Frontmatter {
+            sidebar_position: module + 2,
+            description: file.doc_comment.as_deref().map(doc_summary).unwrap_or_else(|| format!("API reference for {}", module_name)),
+            tags: vec![library.name.clone()],
+            custom_edit_url: source_url(library, file, config),
+            ..page_frontmatter(doc_id, &module_name, &module_name)
+        };
+        docs.push(OutputFile {
+            path: page_path(config, doc_id),
+            content: format!("{}{}", frontmatter.render(), generate_file_doc(pages, module, config, resolver)),
+        });
+    }
+
+    // Generate a page for each item, positioned within its module's directory
+    for (module, (_, file)) in pages.module_pages.iter().enumerate() {
+        let module_name = qualified_module_name(&library.name, file);
+        for (position, item_page) in pages.item_pages.iter().filter(|item_page| item_page.module == module).enumerate() {
+            let frontmatter = Frontmatter {
+                sidebar_position: position + 1,
+                description: item_page
+                    .item
+                    .doc_comment()
+                    .map(doc_summary)
+                    .unwrap_or_else(|| format!("API reference for {}::{}", module_name, item_page.item.name())),
+                tags: vec![library.name.clone()],
+                custom_edit_url: source_url(library, file, config),
+                ..page_frontmatter(&item_page.id, item_page.item.name(), item_page.item.name())
+            };
+            docs.push(OutputFile {
+                path: page_path(config, &item_page.id),
+                content: format!("{}{}", frontmatter.render(), generate_item_doc(pages, item_page, config, resolver)),
+            });
+        }
+    }
+
+    docs
+}
+
+// A category per crate, opening its index page, with modules nested by path and each module's
+// items beneath its page
+fn library_sidebar(pages: &LibraryPages, config: &Config) -> SidebarItem {
+    SidebarItem::Category {
+        label: pages.library.name.clone(),
+        link: Some(CategoryLink::Doc(pages.index_id.clone())),
+        collapsible: config.output.sidebar_collapsible,
+        collapsed: config.output.sidebar_collapsible && config.output.sidebar_collapsed_depth == 0,
+        items: module_sidebar(pages, &[], 1, config),
+    }
+}
+
+// Entries for the modules directly below `parent`, in page order
+fn module_sidebar(pages: &LibraryPages, parent: &[String], depth: usize, config: &Config) -> Vec<SidebarItem> {
+    let mut children: Vec<&str> = Vec::new();
+    let mut items = Vec::new();
+
+    for (module, (_, file)) in pages.module_pages.iter().enumerate() {
+        // Files at the crate root, e.g. `lib.nr`, are listed under their own name
+        if parent.is_empty() && file.module_path.is_empty() {
+            items.push(module_entry(pages, module, &file.name, Vec::new(), depth, config));
+        } else if file.module_path.len() > parent.len() && file.module_path.starts_with(parent) {
+            let name = file.module_path[parent.len()].as_str();
+            if !children.contains(&name) {
+                children.push(name);
+            }
+        }
+    }
+
+    for name in children {
+        let mut path = parent.to_vec();
+        path.push(name.to_string());
+        let submodules = module_sidebar(pages, &path, depth + 1, config);
+        match pages.module_pages.iter().position(|(_, file)| file.module_path == path) {
+            Some(module) => items.push(module_entry(pages, module, name, submodules, depth, config)),
+            None => items.push(SidebarItem::Category {
+                label: name.to_string(),
+                link: Some(CategoryLink::GeneratedIndex {
+                    title: format!("{}::{}", pages.library.name, path.join("::")),
+                    slug: format!("/{}/{}", pages.library.name, path.join("/")),
+                }),
+                collapsible: config.output.sidebar_collapsible,
+                collapsed: config.output.sidebar_collapsible && depth >= config.output.sidebar_collapsed_depth,
+                items: submodules,
+            }),
+        }
+    }
+
+    items
+}
+
+// A module page with neither items nor submodules is a plain doc entry
+fn module_entry(pages: &LibraryPages, module: usize, label: &str, submodules: Vec<SidebarItem>, depth: usize, config: &Config) -> SidebarItem {
+    let doc_id = &pages.module_pages[module].0;
+    let mut items = submodules;
+    items.extend(item_entries(pages, module, config));
+    if items.is_empty() {
+        return SidebarItem::Doc {
+            id: doc_id.to_string(),
+            label: label.to_string(),
+        };
+    }
+    SidebarItem::Category {
+        label: label.to_string(),
+        link: Some(CategoryLink::Doc(doc_id.to_string())),
+        collapsible: config.output.sidebar_collapsible,
+        collapsed: config.output.sidebar_collapsible && depth >= config.output.sidebar_collapsed_depth,
+        items,
+    }
+}
+
+// Items with their own pages are doc entries, otherwise links to their anchors on the module page
+fn item_entries(pages: &LibraryPages, module: usize, config: &Config) -> Vec<SidebarItem> {
+    let (doc_id, file) = &pages.module_pages[module];
+    if config.output.page_mode == PageMode::Item {
+        return pages
+            .item_pages
+            .iter()
+            .filter(|item_page| item_page.module == module)
+            .map(|item_page| SidebarItem::Doc {
+                id: item_page.id.clone(),
+                label: item_page.item.name().to_string(),
+            })
+            .collect();
+    }
+
+    let page_url = format!("{}/{}", config.base_path().trim_end_matches('/'), doc_id);
+    file_items(file, config.source.order)
+        .into_iter()
+        .map(|item| SidebarItem::Link {
+            label: item.name().to_string(),
+            href: format!("{}#{}", page_url, item.anchor()),
+        })
+        .collect()
+}
+
+// Sidebars are written as JSON, which is also valid JavaScript and TypeScript
+fn generate_sidebar_file(sidebar: &[SidebarItem], sidebar_id: &str, format: SidebarFormat) -> String {
+    let mut sidebars = Map::new();
+    sidebars.insert(sidebar_id.to_string(), Value::Array(sidebar.iter().map(sidebar_json).collect()));
+    let sidebars = serde_json::to_string_pretty(&sidebars).expect("sidebars serialize to JSON");
+
+    match format {
+        SidebarFormat::Ts => format!(
+            "import type {{SidebarsConfig}} from '@docusaurus/plugin-content-docs';\n\nconst sidebars: SidebarsConfig = {};\n\nexport default sidebars;\n",
+            sidebars
+        ),
+        _ => format!("module.exports = {};\n", sidebars),
+    }
+}
+
+// Just the items, for a hand-written sidebars file to use as e.g. `api: require('./sidebars-api.js')`
+fn generate_sidebar_module(sidebar: &[SidebarItem], format: SidebarFormat) -> String {
+    let items = sidebar_items_json(sidebar);
+    match format {
+        SidebarFormat::Ts => format!(
+            "import type {{SidebarsConfig}} from '@docusaurus/plugin-content-docs';\n\nconst items: SidebarsConfig[string] = {};\n\nexport default items;\n",
+            items
+        ),
+        _ => format!("module.exports = {};\n", items),
+    }
+}
+
+fn patch_sidebar_file(sidebar: &[SidebarItem], config: &Config) -> Result<String, Error> {
+    let path = config.sidebar_path();
+    let source = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+    let category = config.output.sidebar_category.as_deref();
+    sidebar_patch::patch_sidebar(&source, &config.output.sidebar_id, category, &sidebar_items_json(sidebar)).map_err(|message| Error::Config {
+        path: Some(path.clone()),
+        key: Some(if category.is_some() { "output.sidebar_category" } else { "output.sidebar_id" }.to_string()),
+        span: None,
+        message,
+    })
+}
+
+fn sidebar_items_json(sidebar: &[SidebarItem]) -> String {
+    let items = Value::Array(sidebar.iter().map(sidebar_json).collect());
+    serde_json::to_string_pretty(&items).expect("sidebars serialize to JSON")
+}
+
+fn sidebar_json(item: &SidebarItem) -> Value {
+    match item {
+        SidebarItem::Category { label, link, collapsible, collapsed, items } => {
+            let mut category = Map::new();
+            category.insert("type".to_string(), json!("category"));
+            category.insert("label".to_string(), json!(label));
+            category.insert("collapsible".to_string(), json!(collapsible));
+            category.insert("collapsed".to_string(), json!(collapsed));
+            if let Some(link) = link {
+                category.insert("link".to_string(), category_link_json(link));
+            }
+            category.insert("items".to_string(), Value::Array(items.iter().map(sidebar_json).collect()));
+            Value::Object(category)
+        }
+        SidebarItem::Doc { id, label } => json!({ "type": "doc", "id": id, "label": label }),
+        SidebarItem::Link { label, href } => json!({ "type": "link", "label": label, "href": href }),
+    }
+}
+
+fn category_link_json(link: &CategoryLink) -> Value {
+    match link {
+        CategoryLink::Doc(id) => json!({ "type": "doc", "id": id }),
+        CategoryLink::GeneratedIndex { title, slug } => json!({ "type": "generated-index", "title": title, "slug": slug }),
+    }
+}
+
+// For autogenerated sidebars, a `_category_.json` in every directory of the docs: one per crate
+// and one per module directory, positioned like the pages they contain
+fn generate_category_files(site: &Site, config: &Config) -> Vec<OutputFile> {
+    let mut files = Vec::new();
+    let category = |label: &str, position: usize, depth: usize, link: CategoryLink| {
+        let collapsible = config.output.sidebar_collapsible;
+        let content = json!({
+            "label": label,
+            "position": position,
+            "collapsible": collapsible,
+            "collapsed": collapsible && depth >= config.output.sidebar_collapsed_depth,
+            "link": category_link_json(&link),
+        });
+        serde_json::to_string_pretty(&content).expect("categories serialize to JSON") + "\n"
+    };
+
+    for (index, pages) in site.libraries.iter().enumerate() {
+        let crate_dir = pages.index_id.rsplit_once('/').map_or(pages.index_id.as_str(), |(dir, _)| dir);
+        files.push(OutputFile {
+            path: config.output.docs_dir.join(crate_dir).join(CATEGORY_FILE),
+            content: category(&pages.library.name, index + 2, 0, CategoryLink::Doc(pages.index_id.clone())),
+        });
+
+        // Module directories, e.g. `aztec/context` for `aztec/context/private_context`
+        let mut module_dirs: Vec<(&str, usize)> = Vec::new();
+        let module_ids = pages.module_pages.iter().enumerate().map(|(module, (doc_id, _))| (doc_id, module));
+        let item_ids = pages.item_pages.iter().map(|item_page| (&item_page.id, item_page.module));
+        for (doc_id, module) in module_ids.chain(item_ids) {
+            let mut dir = doc_id.as_str();
+            while let Some((parent, _)) = dir.rsplit_once('/') {
+                if parent == crate_dir || !parent.starts_with(crate_dir) {
+                    break;
+                }
+                if !module_dirs.iter().any(|(module_dir, _)| *module_dir == parent) {
+                    // Module pages are positioned from 2, after the crate index
+                    module_dirs.push((parent, module + 2));
+                }
+                dir = parent;
+            }
+        }
+
+        for (dir, position) in module_dirs {
+            let segments: Vec<&str> = dir.split('/').collect();
+            // The module's page is in the folder as its `index`, unless another page took that ID
+            let index_id = format!("{}/index", dir);
+            let module_page = pages.module_pages.iter().find(|(doc_id, _)| doc_id == dir).or_else(|| pages.module_pages.iter().find(|(doc_id, _)| *doc_id == index_id));
+            let link = match module_page {
+                Some((doc_id, _)) => CategoryLink::Doc(doc_id.clone()),
+                None => CategoryLink::GeneratedIndex {
+                    title: format!("{}::{}", pages.library.name, segments[1..].join("::")),
+                    slug: format!("/{}", dir),
+                },
+            };
+            files.push(OutputFile {
+                path: config.output.docs_dir.join(dir).join(CATEGORY_FILE),
+                content: category(segments[segments.len() - 1], position, segments.len() - 1, link),
+            });
+        }
+    }
+
+    files
+}
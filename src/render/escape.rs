@@ -0,0 +1,125 @@
+//! Escaping for text written into Markdown pages. MDX (Docusaurus v2/v3) reads `<` as the start
+//! of a JSX tag and `{`/`}` as an expression, and plain Markdown swallows `<T>` as an HTML tag.
+//! Backslash escapes are valid in both, so every Markdown backend shares this layer. Code spans
+//! and fenced code blocks are left untouched.
+
+/// Escapes prose, e.g. a doc comment or the intro text.
+pub fn escape_markdown(text: &str) -> String {
+    escape(text, false)
+}
+
+/// Escapes the text of a heading, before any `{#anchor}` ID is appended.
+pub fn escape_heading(text: &str) -> String {
+    escape(&text.replace('\n', " "), false)
+}
+
+/// Escapes a table cell, which additionally must not contain `|` or line breaks.
+pub fn escape_table_cell(text: &str) -> String {
+    escape(&text.replace('\n', " "), true)
+}
+
+fn escape(text: &str, in_table: bool) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut content = String::with_capacity(text.len());
+    let mut i = 0;
+    let mut at_line_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // Fenced code blocks are copied up to and including their closing fence
+        if at_line_start && starts_fence(&chars, i) {
+            let end = fence_end(&chars, i);
+            content.extend(&chars[i..end]);
+            i = end;
+            continue;
+        }
+        at_line_start = c == '\n' || (at_line_start && c.is_whitespace());
+
+        match c {
+            // Already escaped characters stay as they are
+            '\\' if i + 1 < chars.len() => {
+                content.push(c);
+                content.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            '`' => {
+                let end = code_span_end(&chars, i);
+                let span: String = chars[i..end].iter().collect();
+                // Inside tables a `|` ends the cell even within code
+                content.push_str(&if in_table { span.replace('|', "\\|") } else { span });
+                i = end;
+                continue;
+            }
+            '<' if is_autolink(&chars, i) => content.push(c),
+            '<' | '{' | '}' => {
+                content.push('\\');
+                content.push(c);
+            }
+            '|' if in_table => content.push_str("\\|"),
+            _ => content.push(c),
+        }
+        i += 1;
+    }
+
+    content
+}
+
+fn starts_fence(chars: &[char], i: usize) -> bool {
+    let rest: String = chars[i..].iter().take(3).collect();
+    rest == "```" || rest == "~~~"
+}
+
+// Index just past the closing fence's line, or the end of the text for an unclosed fence
+fn fence_end(chars: &[char], start: usize) -> usize {
+    let fence = chars[start];
+    let mut i = start;
+    // Skip the opening fence line
+    while i < chars.len() && chars[i] != '\n' {
+        i += 1;
+    }
+    while i < chars.len() {
+        i += 1;
+        let line_start = i;
+        while i < chars.len() && chars[i] == ' ' {
+            i += 1;
+        }
+        if i + 3 <= chars.len() && chars[i..i + 3].iter().all(|c| *c == fence) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            return i;
+        }
+        i = line_start;
+        while i < chars.len() && chars[i] != '\n' {
+            i += 1;
+        }
+    }
+    chars.len()
+}
+
+// A code span closes at the next backtick run of the same length; an unmatched run is literal
+fn code_span_end(chars: &[char], start: usize) -> usize {
+    let run = chars[start..].iter().take_while(|c| **c == '`').count();
+    let mut i = start + run;
+    while i < chars.len() {
+        if chars[i] == '`' {
+            let closing = chars[i..].iter().take_while(|c| **c == '`').count();
+            if closing == run {
+                return i + closing;
+            }
+            i += closing;
+        } else {
+            i += 1;
+        }
+    }
+    start + run
+}
+
+// `<https://...>` is a Markdown autolink, which MDX also accepts
+fn is_autolink(chars: &[char], i: usize) -> bool {
+    let rest: String = chars[i + 1..].iter().take_while(|c| **c != '>' && !c.is_whitespace()).collect();
+    let closed = chars.get(i + 1 + rest.chars().count()) == Some(&'>');
+    closed && (rest.starts_with("http://") || rest.starts_with("https://"))
+}
@@ -0,0 +1,272 @@
+//! The static HTML backend.
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::generator::{qualified_module_name, LibraryPages, Site};
+use crate::inventory::LinkResolver;
+use crate::render::markdown::{generate_file_doc, generate_item_doc};
+use crate::render::{OutputFile, Renderer};
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag};
+use std::path::PathBuf;
+
+pub(crate) const STATIC_DIR: &str = "static";
+const STYLESHEET: &str = include_str!("noirdoc.css");
+const GENERATOR_META: &str = "<meta name=\"generator\" content=\"noirdoc\">";
+
+const KEYWORDS: &[&str] = &[
+    "as", "assert", "assert_eq", "comptime", "const", "constrain", "crate", "dep", "else", "fn", "for", "global", "if", "impl", "in",
+    "let", "mod", "mut", "pub", "return", "self", "Self", "struct", "trait", "type", "unconstrained", "use", "where",
+];
+const PRIMITIVES: &[&str] = &[
+    "bool", "Field", "str", "u1", "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64",
+];
+
+/// A self-contained static site: every page links its stylesheet and other pages relatively and
+/// highlighting is done at build time, so it works from `file://` without any JavaScript.
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn page_extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn served_extension(&self) -> Option<&'static str> {
+        Some("html")
+    }
+
+    fn render(&self, site: &Site, config: &Config, resolver: &LinkResolver) -> Result<Vec<OutputFile>, Error> {
+        let mut files = Vec::new();
+
+        // The overview is the site's entry point, so it is written as the root `index.html`
+        files.push(OutputFile {
+            path: PathBuf::from("index.html"),
+            content: generate_page(site, config, "index", &config.site.title, &generate_main_overview(site, config)),
+        });
+
+        for pages in &site.libraries {
+            files.push(OutputFile {
+                path: PathBuf::from(format!("{}.html", pages.index_id)),
+                content: generate_page(site, config, &pages.index_id, &pages.library.name, &generate_library_overview(pages)),
+            });
+
+            for (module, (doc_id, file)) in pages.module_pages.iter().enumerate() {
+                let module_name = qualified_module_name(&pages.library.name, file);
+                let markdown = generate_file_doc(pages, module, config, resolver);
+                files.push(OutputFile {
+                    path: PathBuf::from(format!("{}.html", doc_id)),
+                    content: generate_page(site, config, doc_id, &module_name, &markdown_to_html(&markdown)),
+                });
+            }
+
+            for item_page in &pages.item_pages {
+                let markdown = generate_item_doc(pages, item_page, config, resolver);
+                files.push(OutputFile {
+                    path: PathBuf::from(format!("{}.html", item_page.id)),
+                    content: generate_page(site, config, &item_page.id, item_page.item.name(), &markdown_to_html(&markdown)),
+                });
+            }
+        }
+
+        files.push(OutputFile {
+            path: PathBuf::from(STATIC_DIR).join("noirdoc.css"),
+            content: STYLESHEET.to_string(),
+        });
+
+        Ok(files)
+    }
+}
+
+// Prefix that leads from the page `doc_id` back to the output root
+fn root_prefix(doc_id: &str) -> String {
+    "../".repeat(doc_id.matches('/').count())
+}
+
+fn generate_page(site: &Site, config: &Config, doc_id: &str, title: &str, body: &str) -> String {
+    let root = root_prefix(doc_id);
+    let mut content = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    content.push_str("<meta charset=\"utf-8\">\n");
+    content.push_str(&format!("{}\n", GENERATOR_META));
+    content.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    content.push_str(&format!("<title>{} - {}</title>\n", escape_html(title), escape_html(&config.site.title)));
+    content.push_str(&format!("<link rel=\"stylesheet\" href=\"{}{}/noirdoc.css\">\n", root, STATIC_DIR));
+    content.push_str("</head>\n<body>\n");
+    content.push_str(&generate_sidebar(site, config, doc_id));
+    content.push_str(&format!("<main>\n{}</main>\n", body));
+    content.push_str("</body>\n</html>\n");
+    content
+}
+
+fn generate_sidebar(site: &Site, config: &Config, doc_id: &str) -> String {
+    let root = root_prefix(doc_id);
+    let link = |target_id: &str, label: &str| {
+        let class = if target_id == doc_id { " class=\"current\"" } else { "" };
+        format!("<a href=\"{}{}.html\"{}>{}</a>", root, target_id, class, escape_html(label))
+    };
+
+    let mut content = String::from("<nav class=\"sidebar\">\n");
+    content.push_str(&format!("<p class=\"site-title\">{}</p>\n<ul>\n", link("index", &config.site.title)));
+    for pages in &site.libraries {
+        content.push_str(&format!("<li>{}\n<ul>\n", link(&pages.index_id, &pages.library.name)));
+        for (module, (module_id, file)) in pages.module_pages.iter().enumerate() {
+            content.push_str(&format!("<li>{}", link(module_id, &qualified_module_name(&pages.library.name, file))));
+            // Item pages are only listed under the module being viewed
+            let item_pages: Vec<_> = pages.item_pages.iter().filter(|item_page| item_page.module == module).collect();
+            if !item_pages.is_empty() && (module_id == doc_id || item_pages.iter().any(|item_page| item_page.id == doc_id)) {
+                content.push_str("\n<ul>\n");
+                for item_page in item_pages {
+                    content.push_str(&format!("<li>{}</li>\n", link(&item_page.id, item_page.item.name())));
+                }
+                content.push_str("</ul>\n");
+            }
+            content.push_str("</li>\n");
+        }
+        content.push_str("</ul>\n</li>\n");
+    }
+    content.push_str("</ul>\n</nav>\n");
+    content
+}
+
+fn generate_main_overview(site: &Site, config: &Config) -> String {
+    let mut content = format!("<h1>{}</h1>\n", escape_html(&config.site.title));
+    content.push_str(&markdown_to_html(&config.site.intro));
+    content.push_str("<ul>\n");
+    for pages in &site.libraries {
+        content.push_str(&format!("<li><a href=\"{}.html\">{}</a></li>\n", pages.index_id, escape_html(&pages.library.name)));
+    }
+    content.push_str("</ul>\n");
+    content
+}
+
+fn generate_library_overview(pages: &LibraryPages) -> String {
+    let root = root_prefix(&pages.index_id);
+    let mut content = format!("<h1>{} Library</h1>\n", escape_html(&pages.library.name));
+    content.push_str("<p>This library contains the following modules:</p>\n<ul>\n");
+    for (doc_id, file) in &pages.module_pages {
+        let module_name = qualified_module_name(&pages.library.name, file);
+        content.push_str(&format!("<li><a href=\"{}{}.html\">{}</a></li>\n", root, doc_id, escape_html(&module_name)));
+    }
+    content.push_str("</ul>\n");
+    content
+}
+
+// Renders the shared Markdown, keeping `{#anchor}` heading IDs and highlighting Noir code blocks
+fn markdown_to_html(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+
+    let mut events = Vec::new();
+    let mut code_block: Option<String> = None;
+    // Headings without an explicit ID get a slug of their text, as Docusaurus would assign
+    let mut heading: Option<(usize, Vec<Event>)> = None;
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::Heading(level, None, _)) => heading = Some((level as usize, Vec::new())),
+            Event::End(Tag::Heading(..)) if heading.is_some() => {
+                let (level, inner) = heading.take().unwrap();
+                let text: String = inner
+                    .iter()
+                    .filter_map(|event| match event {
+                        Event::Text(text) | Event::Code(text) => Some(text.to_string()),
+                        _ => None,
+                    })
+                    .collect();
+                events.push(Event::Html(CowStr::from(format!("<h{} id=\"{}\">", level, slug(&text)))));
+                events.extend(inner);
+                events.push(event);
+            }
+            event if heading.is_some() => heading.as_mut().unwrap().1.push(event),
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(ref lang))) if is_noir(lang) => code_block = Some(String::new()),
+            Event::Text(text) if code_block.is_some() => code_block.as_mut().unwrap().push_str(&text),
+            Event::End(Tag::CodeBlock(_)) if code_block.is_some() => {
+                let code = code_block.take().unwrap();
+                events.push(Event::Html(CowStr::from(format!(
+                    "<pre class=\"signature\"><code>{}</code></pre>\n",
+                    highlight_noir(code.trim_end())
+                ))));
+            }
+            event => events.push(event),
+        }
+    }
+
+    let mut content = String::new();
+    html::push_html(&mut content, events.into_iter());
+    content
+}
+
+// `Table of Contents` -> `table-of-contents`
+fn slug(text: &str) -> String {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+    words.join("-")
+}
+
+fn is_noir(lang: &str) -> bool {
+    matches!(lang, "rust" | "noir" | "nr")
+}
+
+// Wraps keywords, types, literals and comments in spans styled by the stylesheet
+pub(crate) fn highlight_noir(code: &str) -> String {
+    let mut content = String::new();
+    let chars: Vec<char> = code.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            push_token(&mut content, "comment", &chars[start..i]);
+        } else if c == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(chars.len());
+            push_token(&mut content, "string", &chars[start..i]);
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            push_token(&mut content, "number", &chars[start..i]);
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let class = if KEYWORDS.contains(&word.as_str()) {
+                "keyword"
+            } else if PRIMITIVES.contains(&word.as_str()) || word.starts_with(|c: char| c.is_uppercase()) {
+                "type"
+            } else if chars.get(i) == Some(&'(') {
+                "function"
+            } else {
+                ""
+            };
+            push_token(&mut content, class, &chars[start..i]);
+        } else {
+            content.push_str(&escape_html(&c.to_string()));
+            i += 1;
+        }
+    }
+
+    content
+}
+
+fn push_token(content: &mut String, class: &str, token: &[char]) {
+    let token: String = token.iter().collect();
+    if class.is_empty() {
+        content.push_str(&escape_html(&token));
+    } else {
+        content.push_str(&format!("<span class=\"{}\">{}</span>", class, escape_html(&token)));
+    }
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
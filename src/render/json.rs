@@ -0,0 +1,33 @@
+//! The JSON model backend.
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::generator::Site;
+use crate::inventory::LinkResolver;
+use crate::model::{self, MODEL_FILE, MODEL_SCHEMA, MODEL_SCHEMA_FILE};
+use crate::render::{OutputFile, Renderer};
+use std::path::PathBuf;
+
+/// The documentation model as `model.json`, next to the JSON Schema describing it.
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    // Pages are referred to by doc ID, so the extension never appears in the model
+    fn page_extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn render(&self, site: &Site, _config: &Config, resolver: &LinkResolver) -> Result<Vec<OutputFile>, Error> {
+        let doc_model = model::build_model(site, resolver);
+        Ok(vec![
+            OutputFile {
+                path: PathBuf::from(MODEL_FILE),
+                content: serde_json::to_string_pretty(&doc_model).expect("the model serializes to JSON"),
+            },
+            OutputFile {
+                path: PathBuf::from(MODEL_SCHEMA_FILE),
+                content: MODEL_SCHEMA.to_string(),
+            },
+        ])
+    }
+}
@@ -0,0 +1,136 @@
+//! The `llms.txt` backend.
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::generator::{constant_signature, doc_summary, function_signature, impl_owner, qualified_module_name, type_alias_signature, ImplDoc, LibraryPages, Site};
+use crate::inventory::{self, LinkResolver};
+use crate::parser::{NoirFunction, NoirStruct};
+use crate::render::{OutputFile, Renderer};
+use std::path::PathBuf;
+
+pub(crate) const LLMS_TXT_FILE: &str = "llms.txt";
+pub(crate) const LLMS_TXT_DIR: &str = "llms";
+
+/// The public API as one compact digest: `llms.txt` for every crate and `llms/<crate>.txt` per
+/// crate. Each line is an item's full path, its signature and the first sentence of its docs,
+/// so a grep for a name returns everything needed to use it.
+pub struct LlmsTxtRenderer;
+
+impl Renderer for LlmsTxtRenderer {
+    fn page_extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn render(&self, site: &Site, config: &Config, _resolver: &LinkResolver) -> Result<Vec<OutputFile>, Error> {
+        let mut files = Vec::new();
+        let mut content = format!("# {}\n\n", config.site.title);
+        if !config.site.intro.is_empty() {
+            content.push_str(&format!("> {}\n\n", config.site.intro));
+        }
+
+        for pages in &site.libraries {
+            let digest = generate_crate_digest(pages);
+            content.push_str(&format!("## {}\n\n{}", pages.library.name, digest));
+            files.push(OutputFile {
+                path: PathBuf::from(LLMS_TXT_DIR).join(format!("{}.txt", pages.library.name)),
+                content: format!("# {}\n\n{}", pages.library.name, digest),
+            });
+        }
+
+        files.insert(
+            0,
+            OutputFile {
+                path: PathBuf::from(LLMS_TXT_FILE),
+                content,
+            },
+        );
+        Ok(files)
+    }
+}
+
+fn generate_crate_digest(pages: &LibraryPages) -> String {
+    let mut content = String::new();
+
+    for (module, (_, file)) in pages.module_pages.iter().enumerate() {
+        let module_name = qualified_module_name(&pages.library.name, file);
+        let mut lines = Vec::new();
+
+        for struct_item in file.structs.iter().filter(|struct_item| struct_item.is_public) {
+            lines.push(digest_line(&format!("{}::{}", module_name, struct_item.name), &struct_signature(struct_item), struct_item.doc_comment.as_deref()));
+        }
+        for trait_item in file.traits.iter().filter(|trait_item| trait_item.is_public) {
+            let trait_path = format!("{}::{}", module_name, trait_item.name);
+            lines.push(digest_line(&trait_path, &format!("trait {}", trait_item.name), trait_item.doc_comment.as_deref()));
+            for method in &trait_item.methods {
+                lines.push(function_line(&trait_path, method));
+            }
+        }
+        for type_alias in file.type_aliases.iter().filter(|type_alias| type_alias.is_public) {
+            lines.push(digest_line(&format!("{}::{}", module_name, type_alias.name), &type_alias_signature(type_alias), type_alias.doc_comment.as_deref()));
+        }
+        for function in file.functions.iter().filter(|function| function.is_public) {
+            lines.push(function_line(&module_name, function));
+        }
+        for constant in file.constants.iter().filter(|constant| constant.is_public) {
+            lines.push(digest_line(&format!("{}::{}", module_name, constant.name), &constant_signature(constant), constant.doc_comment.as_deref()));
+        }
+        // Methods are listed with the module of the struct they belong to. Methods of trait impls
+        // are as visible as the trait, so they are listed without a `pub` of their own.
+        for impl_doc in pages.impl_docs.iter().filter(|impl_doc| impl_doc.owner.map_or(impl_doc.module, |(owner, _)| owner) == module) {
+            if !target_is_public(pages, impl_doc) {
+                continue;
+            }
+            let parent_path = inventory::method_parent_path(pages, impl_doc);
+            let is_trait_impl = impl_doc.impl_item.trait_name.is_some();
+            for method in impl_doc.impl_item.methods.iter().filter(|method| is_trait_impl || method.is_public) {
+                lines.push(function_line(&parent_path, method));
+            }
+        }
+
+        if !lines.is_empty() {
+            content.push_str(&format!("### {}\n\n{}\n", module_name, lines.join("")));
+        }
+    }
+
+    content
+}
+
+fn function_line(parent_path: &str, function: &NoirFunction) -> String {
+    digest_line(&format!("{}::{}", parent_path, function.name), &function_signature(function), function.doc_comment.as_deref())
+}
+
+// - aztec::context::PrivateContext::msg_sender: `fn msg_sender() -> AztecAddress` - Returns the sender.
+fn digest_line(path: &str, signature: &str, doc_comment: Option<&str>) -> String {
+    // With nothing to resolve against, intra-doc links are rendered as plain code spans
+    let summary = doc_comment.map(|doc_comment| LinkResolver::default().link_code_spans(&doc_summary(doc_comment), "", ""));
+    match summary.filter(|summary| !summary.is_empty()) {
+        Some(summary) => format!("- {}: `{}` - {}\n", path, signature, summary),
+        None => format!("- {}: `{}`\n", path, signature),
+    }
+}
+
+// Impls of private structs of the crate are left out with the struct; types from elsewhere are
+// assumed public
+fn target_is_public(pages: &LibraryPages, impl_doc: &ImplDoc) -> bool {
+    let target = inventory::base_type_path(&impl_doc.impl_item.target);
+    match impl_doc.owner.or_else(|| impl_owner(pages, impl_doc.module, &target)) {
+        Some((module, name)) => pages.module_pages[module].1.structs.iter().any(|struct_item| struct_item.name == name && struct_item.is_public),
+        None => true,
+    }
+}
+
+// `struct Note<T> { owner: AztecAddress, value: T }`, with `..` standing in for private fields
+fn struct_signature(struct_item: &NoirStruct) -> String {
+    let mut signature = format!("struct {}", struct_item.name);
+    if !struct_item.generic_params.is_empty() {
+        signature.push_str(&format!("<{}>", struct_item.generic_params.join(", ")));
+    }
+    let mut fields: Vec<String> = struct_item.fields.iter().filter(|field| field.is_public).map(|field| format!("{}: {}", field.name, field.ty)).collect();
+    if fields.len() < struct_item.fields.len() {
+        fields.push("..".to_string());
+    }
+    if !fields.is_empty() {
+        signature.push_str(&format!(" {{ {} }}", fields.join(", ")));
+    }
+    signature
+}
@@ -0,0 +1,339 @@
+use crate::config::{Config, PageMode};
+use crate::ordering::OrderStrategy;
+use crate::generator::{constant_signature, doc_summary, file_items, function_signature, impl_signature, parse_doc_comment, qualified_module_name, type_alias_signature, ImplDoc, ItemPage, Library, LibraryPages, PageItem, TraitImpl};
+use crate::inventory::{self, LinkResolver};
+use crate::parser::{NoirConstant, NoirFile, NoirFunction, NoirStruct, NoirTrait, NoirTypeAlias};
+use crate::render::escape::{escape_heading, escape_markdown, escape_table_cell};
+
+// Markdown shared by the Markdown-based backends; links between pages come from the resolver,
+// so they already carry the backend's page extension
+
+// Relative link from one doc to another, using the `.md` source paths Docusaurus and mdBook resolve
+pub(crate) fn relative_doc_link(from_id: &str, to_id: &str) -> String {
+    let depth = from_id.matches('/').count();
+    format!("{}{}.md", "../".repeat(depth), to_id)
+}
+
+pub(crate) fn generate_main_overview(config: &Config, overview_id: &str, library_pages: &[LibraryPages]) -> String {
+    let mut content = String::from(&format!("# {}\n\n", escape_heading(&config.site.title)));
+    content.push_str(&format!("{}\n\n", escape_markdown(&config.site.intro)));
+    
+    for pages in library_pages {
+        content.push_str(&format!("- [{}]({})\n", pages.library.name, relative_doc_link(overview_id, &pages.index_id)));
+    }
+
+    content
+}
+
+pub(crate) fn generate_library_overview(library: &Library, index_id: &str, module_pages: &[(String, &NoirFile)]) -> String {
+    let mut content = String::from(&format!("# {} Library\n\n", library.name));
+    content.push_str("This library contains the following modules:\n\n");
+    
+    for (doc_id, file) in module_pages {
+        let module_name = qualified_module_name(&library.name, file);
+        content.push_str(&format!("- [{}]({})\n", module_name, relative_doc_link(index_id, doc_id)));
+    }
+
+    content
+}
+
+pub(crate) fn generate_file_doc(pages: &LibraryPages, module: usize, config: &Config, resolver: &LinkResolver) -> String {
+    let (doc_id, file) = &pages.module_pages[module];
+    let module_name = qualified_module_name(&pages.library.name, file);
+    let mut content = String::from(&format!("# {} Module\n\n", module_name));
+    if let Some(source_url) = source_url(pages.library, file, config) {
+        content.push_str(&format!("[View source]({})\n\n", source_url));
+    }
+    if let Some(doc_comment) = &file.doc_comment {
+        content.push_str(&format!("{}\n\n", escape_markdown(&resolver.link_code_spans(doc_comment, &pages.library.name, doc_id))));
+    }
+    match config.output.page_mode {
+        PageMode::Module => content.push_str(&generate_file_content(pages, module, config.source.order, resolver)),
+        PageMode::Item => {
+            content.push_str(&generate_module_summary(file, &pages.library.name, doc_id, &module_name, config.source.order, resolver));
+            content.push_str(&generate_impls(pages, module, resolver));
+        }
+    }
+    content
+}
+
+// A page of its own for one item, titled e.g. `Struct PrivateContext`
+pub(crate) fn generate_item_doc(pages: &LibraryPages, page: &ItemPage, config: &Config, resolver: &LinkResolver) -> String {
+    let file = pages.module_pages[page.module].1;
+    let module_name = qualified_module_name(&pages.library.name, file);
+    let mut content = format!("# {} {}\n\n", page.item.kind(), escape_heading(page.item.name()));
+    content.push_str(&format!("`{}::{}`\n\n", module_name, page.item.name()));
+    if let Some(source_url) = source_url(pages.library, file, config) {
+        content.push_str(&format!("[View source]({})\n\n", source_url));
+    }
+    content.push_str(&match page.item {
+        PageItem::Struct(struct_item) => {
+            let impls = pages.struct_impls(page.module, &struct_item.name);
+            let mut body = struct_body(pages, page.module, struct_item, &page.id, resolver);
+            if !impls.is_empty() {
+                body.push_str("## Implementations\n\n");
+                body.push_str(&generate_struct_impls(pages, &impls, "###", &page.id, resolver));
+            }
+            body
+        }
+        PageItem::Trait(trait_item) => trait_body(pages, page.module, trait_item, "##", &page.id, resolver),
+        PageItem::TypeAlias(type_alias) => type_alias_body(pages, page.module, type_alias, &page.id, resolver),
+        PageItem::Function(function) => function_body(function, &pages.library.name, &page.id, resolver),
+        PageItem::Constant(constant) => constant_body(constant, &pages.library.name, &page.id, resolver),
+    });
+    content
+}
+
+// Browsable URL of the module's source file, when source links are enabled and the crate's
+// repository is known
+pub(crate) fn source_url(library: &Library, file: &NoirFile, config: &Config) -> Option<String> {
+    let repository_url = library.repository_url.as_ref().filter(|_| config.features.source_links)?;
+    let source_path: Vec<String> = file.path.iter().map(|part| part.to_string_lossy().into_owned()).collect();
+    Some(format!("{}/{}", repository_url.trim_end_matches('/'), source_path.join("/")))
+}
+
+pub(crate) fn generate_file_content(pages: &LibraryPages, module: usize, order: OrderStrategy, resolver: &LinkResolver) -> String {
+    let (doc_id, file) = &pages.module_pages[module];
+    let module_impls = pages.module_impls(module);
+    let items = file_items(file, order);
+    let mut content = String::new();
+    
+    // Add file-level description
+    content.push_str("This module contains the following components:\n\n");
+
+    // One section per kind, or a single one when kinds are interleaved by name
+    let sections: Vec<(&str, Vec<&PageItem>)> = match order {
+        OrderStrategy::Alphabetical => vec![("Items", items.iter().collect())],
+        _ => [("Struct", "Structs"), ("Trait", "Traits"), ("Type Alias", "Type Aliases"), ("Function", "Functions"), ("Constant", "Constants")]
+            .into_iter()
+            .map(|(kind, title)| (title, items.iter().filter(|item| item.kind() == kind).collect()))
+            .collect(),
+    };
+    let sections: Vec<(&str, Vec<&PageItem>)> = sections.into_iter().filter(|(_, items)| !items.is_empty()).collect();
+    
+    // Generate table of contents
+    content.push_str("## Table of Contents\n");
+    for (title, _) in &sections {
+        content.push_str(&format!("- [{}](#{})\n", title, title.to_lowercase().replace(' ', "-")));
+    }
+    if !module_impls.is_empty() { content.push_str("- [Implementations](#implementations)\n"); }
+    content.push('\n');
+
+    for (title, items) in &sections {
+        content.push_str(&format!("## {}\n\n", title));
+        for item in items {
+            content.push_str(&item_section(pages, module, item, doc_id, resolver));
+        }
+    }
+
+    content.push_str(&generate_impls(pages, module, resolver));
+    content
+}
+
+// An item's `###` section on its module page
+fn item_section(pages: &LibraryPages, module: usize, item: &PageItem, doc_id: &str, resolver: &LinkResolver) -> String {
+    match item {
+        PageItem::Struct(struct_item) => {
+            let mut content = format!("### {} {{#{}}}\n\n", struct_item.name, item.anchor());
+            content.push_str(&struct_body(pages, module, struct_item, doc_id, resolver));
+            content.push_str(&generate_struct_impls(pages, &pages.struct_impls(module, &struct_item.name), "####", doc_id, resolver));
+            content
+        }
+        PageItem::Trait(trait_item) => {
+            let mut content = format!("### {} {{#{}}}\n\n", trait_item.name, item.anchor());
+            content.push_str(&trait_body(pages, module, trait_item, "####", doc_id, resolver));
+            content
+        }
+        PageItem::TypeAlias(type_alias) => format!("### {} {{#{}}}\n\n{}", type_alias.name, item.anchor(), type_alias_body(pages, module, type_alias, doc_id, resolver)),
+        PageItem::Function(function) => format!("### `{}` {{#{}}}\n\n{}", function.name, item.anchor(), function_body(function, &pages.library.name, doc_id, resolver)),
+        PageItem::Constant(constant) => format!("### `{}` {{#{}}}\n\n{}", constant.name, item.anchor(), constant_body(constant, &pages.library.name, doc_id, resolver)),
+    }
+}
+
+// One row per item, linking to the item's own page
+fn generate_module_summary(file: &NoirFile, crate_name: &str, doc_id: &str, module_name: &str, order: OrderStrategy, resolver: &LinkResolver) -> String {
+    let items = file_items(file, order);
+    if items.is_empty() {
+        return String::new();
+    }
+
+    let mut content = String::from("## Items\n\n| Kind | Name | Summary |\n|------|------|---------|\n");
+    for item in items {
+        let name = match resolver.url(&format!("{}::{}", module_name, item.name()), crate_name, doc_id) {
+            Some(url) => format!("[`{}`]({})", item.name(), url),
+            None => format!("`{}`", item.name()),
+        };
+        let summary = item.doc_comment().map(doc_summary).unwrap_or_default();
+        content.push_str(&format!("| {} | {} | {} |\n", item.kind(), name, escape_table_cell(&resolver.link_code_spans(&summary, crate_name, doc_id))));
+    }
+    content.push('\n');
+    content
+}
+
+fn doc_block(doc_comment: Option<&str>, crate_name: &str, doc_id: &str, resolver: &LinkResolver) -> String {
+    match doc_comment {
+        Some(doc_comment) => format!("{}\n\n", escape_markdown(&resolver.link_code_spans(doc_comment, crate_name, doc_id))),
+        None => String::new(),
+    }
+}
+
+fn struct_body(pages: &LibraryPages, module: usize, struct_item: &NoirStruct, doc_id: &str, resolver: &LinkResolver) -> String {
+    let mut content = doc_block(struct_item.doc_comment.as_deref(), &pages.library.name, doc_id, resolver);
+    content.push_str("Fields:\n");
+    for field in &struct_item.fields {
+        content.push_str(&format!("- `{}`: {}\n", field.name, resolver.link_type(&field.ty, &pages.library.name, doc_id)));
+    }
+    content.push('\n');
+
+    let path = format!("{}::{}", qualified_module_name(&pages.library.name, pages.module_pages[module].1), struct_item.name);
+    let traits = pages.implemented_traits(&path);
+    if !traits.is_empty() {
+        content.push_str("Implemented traits:\n");
+        for trait_impl in traits {
+            content.push_str(&trait_impl_entry(&trait_impl.trait_name, trait_impl.trait_path.as_deref(), trait_impl, &pages.library.name, doc_id, resolver));
+        }
+        content.push('\n');
+    }
+
+    content.push_str(&used_by(pages, &path, doc_id, resolver));
+    content
+}
+
+// Functions, methods and fields that accept, return or hold the type at `path`
+fn used_by(pages: &LibraryPages, path: &str, doc_id: &str, resolver: &LinkResolver) -> String {
+    let uses = pages.type_uses(path);
+    if uses.is_empty() {
+        return String::new();
+    }
+
+    let mut content = String::from("Used by:\n");
+    for type_use in uses {
+        // Fields have no anchor of their own, so they link to their struct
+        let target = match type_use.kind {
+            "field" => type_use.path.rsplit_once("::").map_or(type_use.path.as_str(), |(owner, _)| owner),
+            _ => type_use.path.as_str(),
+        };
+        let name = match resolver.url(target, &pages.library.name, doc_id) {
+            Some(url) => format!("[`{}`]({})", type_use.path, url),
+            None => format!("`{}`", type_use.path),
+        };
+        content.push_str(&format!("- {} ({})\n", name, type_use.kind));
+    }
+    content.push('\n');
+    content
+}
+
+fn type_alias_body(pages: &LibraryPages, module: usize, type_alias: &NoirTypeAlias, doc_id: &str, resolver: &LinkResolver) -> String {
+    let mut content = doc_block(type_alias.doc_comment.as_deref(), &pages.library.name, doc_id, resolver);
+    content.push_str(&format!("```rust\n{}\n```\n\n", type_alias_signature(type_alias)));
+    let path = format!("{}::{}", qualified_module_name(&pages.library.name, pages.module_pages[module].1), type_alias.name);
+    content.push_str(&used_by(pages, &path, doc_id, resolver));
+    content
+}
+
+// `- [`Serialize < 3 >`](...)`, marked when it comes from a derive
+fn trait_impl_entry(name: &str, path: Option<&str>, trait_impl: &TraitImpl, crate_name: &str, doc_id: &str, resolver: &LinkResolver) -> String {
+    let name = match path.and_then(|path| resolver.url(path, crate_name, doc_id)) {
+        Some(url) => format!("[`{}`]({})", name, url),
+        None => format!("`{}`", name),
+    };
+    let derived = if trait_impl.impl_item.is_none() { " (derived)" } else { "" };
+    format!("- {}{}\n", name, derived)
+}
+
+// `method_heading` is the heading level of the trait's methods, e.g. `####`
+fn trait_body(pages: &LibraryPages, module: usize, trait_item: &NoirTrait, method_heading: &str, doc_id: &str, resolver: &LinkResolver) -> String {
+    let mut content = doc_block(trait_item.doc_comment.as_deref(), &pages.library.name, doc_id, resolver);
+    let path = format!("{}::{}", qualified_module_name(&pages.library.name, pages.module_pages[module].1), trait_item.name);
+    let implementors = pages.implementors(&path);
+    if !implementors.is_empty() {
+        content.push_str("Implementors:\n");
+        for trait_impl in implementors {
+            content.push_str(&trait_impl_entry(&trait_impl.type_name, trait_impl.type_path.as_deref(), trait_impl, &pages.library.name, doc_id, resolver));
+        }
+        content.push('\n');
+    }
+    for method in &trait_item.methods {
+        content.push_str(&format!("{} `{}` {{#{}}}\n\n", method_heading, method.name, inventory::item_anchor("tymethod", &[&trait_item.name, &method.name])));
+        content.push_str(&function_body(method, &pages.library.name, doc_id, resolver));
+    }
+    content
+}
+
+fn function_body(function: &NoirFunction, crate_name: &str, doc_id: &str, resolver: &LinkResolver) -> String {
+    let mut content = doc_block(function.doc_comment.as_deref(), crate_name, doc_id, resolver);
+    content.push_str(&format!("```rust\n{}\n```\n\n", function_signature(function)));
+    content
+}
+
+fn constant_body(constant: &NoirConstant, crate_name: &str, doc_id: &str, resolver: &LinkResolver) -> String {
+    let mut content = doc_block(constant.doc_comment.as_deref(), crate_name, doc_id, resolver);
+    content.push_str(&format!("```rust\n{}\n```\n\n", constant_signature(constant)));
+    content
+}
+
+// The impl blocks of a struct, each headed by its declaration at level `heading`, e.g. `####`
+fn generate_struct_impls(pages: &LibraryPages, impls: &[&ImplDoc], heading: &str, doc_id: &str, resolver: &LinkResolver) -> String {
+    let mut content = String::new();
+    for impl_doc in impls {
+        content.push_str(&format!("{} `{}` {{#{}}}\n\n", heading, impl_signature(impl_doc.impl_item), impl_doc.anchor));
+        // Blocks written away from the struct say where they are
+        if impl_doc.owner.map(|(module, _)| module) != Some(impl_doc.module) {
+            let module_name = qualified_module_name(&pages.library.name, pages.module_pages[impl_doc.module].1);
+            content.push_str(&format!("Defined in `{}`.\n\n", module_name));
+        }
+        content.push_str(&doc_block(impl_doc.impl_item.doc_comment.as_deref(), &pages.library.name, doc_id, resolver));
+        for (method, method_anchor) in impl_doc.impl_item.methods.iter().zip(&impl_doc.method_anchors) {
+            content.push_str(&format!("{}# `{}` {{#{}}}\n\n", heading, method.name, method_anchor));
+            content.push_str(&method_body(method, &pages.library.name, doc_id, resolver));
+        }
+    }
+    content
+}
+
+// Trait impls, and inherent impls of types not documented here
+fn generate_impls(pages: &LibraryPages, module: usize, resolver: &LinkResolver) -> String {
+    let doc_id = &pages.module_pages[module].0;
+    let impls = pages.module_impls(module);
+    let mut content = String::new();
+    if impls.is_empty() {
+        return content;
+    }
+
+    content.push_str("## Implementations\n\n");
+    for impl_doc in impls {
+        content.push_str(&format!("### `{}` {{#{}}}\n\n", impl_signature(impl_doc.impl_item), impl_doc.anchor));
+        content.push_str(&doc_block(impl_doc.impl_item.doc_comment.as_deref(), &pages.library.name, doc_id, resolver));
+        for (method, method_anchor) in impl_doc.impl_item.methods.iter().zip(&impl_doc.method_anchors) {
+            content.push_str(&format!("#### `{}` {{#{}}}\n\n", method.name, method_anchor));
+            content.push_str(&method_body(method, &pages.library.name, doc_id, resolver));
+        }
+    }
+
+    content
+}
+
+// Description, parameter table and signature of an impl method
+fn method_body(method: &NoirFunction, crate_name: &str, doc_id: &str, resolver: &LinkResolver) -> String {
+    let mut content = String::new();
+    if let Some(doc_comment) = &method.doc_comment {
+        let (description, params) = parse_doc_comment(doc_comment);
+        content.push_str(&format!("{}\n\n", escape_markdown(&resolver.link_code_spans(&description, crate_name, doc_id))));
+
+        // Generate parameter table
+        if !params.is_empty() {
+            content.push_str("| Parameter | Type | Description |\n");
+            content.push_str("|-----------|------|-------------|\n");
+            for (name, _, desc) in params {
+                let param_type = method.params.iter()
+                    .find(|p| p.name == name)
+                    .map(|p| resolver.link_type(&p.ty, crate_name, doc_id))
+                    .unwrap_or_else(|| "`Unknown`".to_string());
+                content.push_str(&format!("| `{}` | {} | {} |\n", name, param_type, escape_table_cell(&resolver.link_code_spans(&desc, crate_name, doc_id))));
+            }
+            content.push('\n');
+        }
+    }
+    content.push_str(&format!("```rust\n{}\n```\n\n", function_signature(method)));
+    content
+}
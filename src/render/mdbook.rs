@@ -0,0 +1,87 @@
+//! The mdBook backend.
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::generator::{qualified_module_name, Site};
+use crate::inventory::LinkResolver;
+use crate::render::markdown::{generate_file_doc, generate_item_doc, generate_library_overview, generate_main_overview};
+use crate::render::{OutputFile, Renderer};
+use std::path::PathBuf;
+
+/// A `book.toml` whose `src` is `output.docs_dir`, holding a `SUMMARY.md` and one chapter per
+/// page. Chapter paths match the Docusaurus doc IDs, so they can also be listed in an existing
+/// book's `SUMMARY.md`.
+pub struct MdBookRenderer;
+
+impl Renderer for MdBookRenderer {
+    fn page_extension(&self) -> &'static str {
+        "md"
+    }
+
+    // mdBook turns each chapter into an HTML page
+    fn served_extension(&self) -> Option<&'static str> {
+        Some("html")
+    }
+
+    fn render(&self, site: &Site, config: &Config, resolver: &LinkResolver) -> Result<Vec<OutputFile>, Error> {
+        let mut files = vec![OutputFile {
+            path: chapter_path(config, &site.overview_id),
+            content: generate_main_overview(config, &site.overview_id, &site.libraries),
+        }];
+        let mut summary = String::from("# Summary\n\n");
+        summary.push_str(&format!("[{}]({}.md)\n\n", escape_link_text(&config.site.overview_label), site.overview_id));
+
+        for pages in &site.libraries {
+            files.push(OutputFile {
+                path: chapter_path(config, &pages.index_id),
+                content: generate_library_overview(pages.library, &pages.index_id, &pages.module_pages),
+            });
+            summary.push_str(&format!("- [{}]({}.md)\n", escape_link_text(&pages.library.name), pages.index_id));
+
+            for (module, (doc_id, file)) in pages.module_pages.iter().enumerate() {
+                let module_name = qualified_module_name(&pages.library.name, file);
+                files.push(OutputFile {
+                    path: chapter_path(config, doc_id),
+                    content: generate_file_doc(pages, module, config, resolver),
+                });
+                summary.push_str(&format!("  - [{}]({}.md)\n", escape_link_text(&module_name), doc_id));
+
+                for item_page in pages.item_pages.iter().filter(|item_page| item_page.module == module) {
+                    files.push(OutputFile {
+                        path: chapter_path(config, &item_page.id),
+                        content: generate_item_doc(pages, item_page, config, resolver),
+                    });
+                    summary.push_str(&format!("    - [{}]({}.md)\n", escape_link_text(item_page.item.name()), item_page.id));
+                }
+            }
+        }
+
+        files.push(OutputFile {
+            path: config.output.docs_dir.join("SUMMARY.md"),
+            content: summary,
+        });
+        files.push(OutputFile {
+            path: PathBuf::from("book.toml"),
+            content: generate_book_toml(config),
+        });
+
+        Ok(files)
+    }
+}
+
+fn chapter_path(config: &Config, doc_id: &str) -> PathBuf {
+    config.output.docs_dir.join(format!("{}.md", doc_id))
+}
+
+fn generate_book_toml(config: &Config) -> String {
+    let src: Vec<String> = config.output.docs_dir.iter().map(|part| part.to_string_lossy().into_owned()).collect();
+    let mut content = String::from("[book]\n");
+    content.push_str(&format!("title = {}\n", toml::Value::String(config.site.title.clone())));
+    content.push_str(&format!("src = {}\n", toml::Value::String(src.join("/"))));
+    content
+}
+
+// Brackets would end the link text early in `SUMMARY.md`
+fn escape_link_text(text: &str) -> String {
+    text.replace('[', "\\[").replace(']', "\\]")
+}
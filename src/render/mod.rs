@@ -0,0 +1,157 @@
+//! Output backends.
+
+use crate::config::Config;
+use crate::error::Error;
+use crate::generator::Site;
+use crate::inventory::LinkResolver;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub mod docusaurus;
+pub mod escape;
+pub mod html;
+pub mod json;
+pub mod llms_txt;
+pub(crate) mod markdown;
+pub mod mdbook;
+mod sidebar_patch;
+
+// Lists the files `noirdoc` created in an output directory, the only ones `noirdoc clean` removes
+const MANIFEST_FILE: &str = ".noirdoc-manifest.json";
+
+/// A file produced by a renderer.
+#[derive(Debug)]
+pub struct OutputFile {
+    /// Path relative to the output directory.
+    pub path: PathBuf,
+    /// The file's contents.
+    pub content: String,
+}
+
+/// An output backend. Renderers only see the parsed model and its page layout, so a new format
+/// can be added without touching parsing.
+pub trait Renderer {
+    /// Extension of the page files, used to build links between them, e.g. `md`.
+    fn page_extension(&self) -> &'static str;
+
+    /// Extension the pages are served with once built, e.g. `html`, recorded in inventories for
+    /// other runs to link to. `None` when pages are served by doc ID, as Docusaurus does.
+    fn served_extension(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Renders every page of the site. `resolver` links item paths to the pages they are
+    /// documented on, already carrying [`Renderer::page_extension`].
+    fn render(&self, site: &Site, config: &Config, resolver: &LinkResolver) -> Result<Vec<OutputFile>, Error>;
+
+    /// Undoes what the backend changed in files it did not create, such as a patched sidebars
+    /// file, before `noirdoc clean` removes the files it did. Does nothing by default.
+    fn clean(&self, _config: &Config) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+/// The built-in output backends.
+pub enum OutputFormat {
+    /// Markdown pages and a sidebar for Docusaurus.
+    #[default]
+    Docusaurus,
+    /// A static HTML site.
+    Html,
+    /// The documentation model as JSON.
+    Json,
+    /// `llms.txt` digests for language models.
+    LlmsTxt,
+    /// Markdown chapters and a `SUMMARY.md` for mdBook.
+    #[serde(rename = "mdbook")]
+    MdBook,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "docusaurus" => Ok(OutputFormat::Docusaurus),
+            "html" => Ok(OutputFormat::Html),
+            "json" => Ok(OutputFormat::Json),
+            "llms-txt" => Ok(OutputFormat::LlmsTxt),
+            "mdbook" => Ok(OutputFormat::MdBook),
+            _ => Err(format!("unknown output format '{}', expected 'docusaurus', 'html', 'json', 'llms-txt' or 'mdbook'", s)),
+        }
+    }
+}
+
+/// The renderer of a built-in output format.
+pub fn renderer(format: OutputFormat) -> Box<dyn Renderer> {
+    match format {
+        OutputFormat::Docusaurus => Box::new(docusaurus::DocusaurusRenderer),
+        OutputFormat::Html => Box::new(html::HtmlRenderer),
+        OutputFormat::Json => Box::new(json::JsonRenderer),
+        OutputFormat::LlmsTxt => Box::new(llms_txt::LlmsTxtRenderer),
+        OutputFormat::MdBook => Box::new(mdbook::MdBookRenderer),
+    }
+}
+
+// Writes the files and records the ones created, here or by earlier runs, in the manifest. Files
+// that were there before, such as hand-written docs or a patched sidebars file, are not recorded.
+pub(crate) fn write_output<'a>(files: impl IntoIterator<Item = &'a OutputFile>, output_dir: &Path) -> Result<(), Error> {
+    let mut created = read_manifest(output_dir)?;
+    created.retain(|path| output_dir.join(path).is_file());
+    for file in files {
+        let path = output_dir.join(&file.path);
+        if !path.exists() {
+            created.push(file.path.clone());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+        }
+        fs::write(&path, &file.content).map_err(|e| Error::io(&path, e))?;
+    }
+
+    created.sort();
+    created.dedup();
+    let path = output_dir.join(MANIFEST_FILE);
+    let content = serde_json::to_string_pretty(&created).map_err(|e| Error::io(&path, e.into()))?;
+    fs::write(&path, content).map_err(|e| Error::io(&path, e))
+}
+
+/// Removes the files earlier runs created in `output.dir`, as listed in its manifest, and the
+/// directories left empty, after letting the renderer undo its changes to other files.
+pub fn clean_output(renderer: &dyn Renderer, config: &Config) -> Result<(), Error> {
+    renderer.clean(config)?;
+
+    let output_dir = &config.output.dir;
+    for file in read_manifest(output_dir)? {
+        let path = output_dir.join(&file);
+        if path.is_file() {
+            fs::remove_file(&path).map_err(|e| Error::io(&path, e))?;
+        }
+        // `remove_dir` fails on the first directory that still holds something
+        for dir in file.ancestors().skip(1).filter(|dir| !dir.as_os_str().is_empty()) {
+            if fs::remove_dir(output_dir.join(dir)).is_err() {
+                break;
+            }
+        }
+    }
+
+    let manifest = output_dir.join(MANIFEST_FILE);
+    if manifest.is_file() {
+        fs::remove_file(&manifest).map_err(|e| Error::io(&manifest, e))?;
+    }
+    Ok(())
+}
+
+// Paths relative to the output directory; none before the first run
+fn read_manifest(output_dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let path = output_dir.join(MANIFEST_FILE);
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+    serde_json::from_str(&content).map_err(|e| Error::io(&path, e.into()))
+}
@@ -0,0 +1,207 @@
+// Swap the items of one sidebar, or of one category within it, in a hand-written `sidebars.js`
+// or `sidebars.ts`. Only strings, comments, words and brackets are tokenized, which is enough to
+// find the array to replace; everything around it is kept byte for byte.
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Str(String),
+    Word(String),
+    Punct(char),
+}
+
+// `items` is the JSON array to put in place of the existing one
+pub(crate) fn patch_sidebar(source: &str, sidebar_id: &str, category: Option<&str>, items: &str) -> Result<String, String> {
+    let tokens = tokenize(source);
+
+    let sidebar_open = find_key_array(&tokens, 0, tokens.len(), sidebar_id, false).ok_or_else(|| format!("no sidebar `{}` in the file", sidebar_id))?;
+    let sidebar_close = matching_close(&tokens, sidebar_open).ok_or_else(|| format!("sidebar `{}` is not closed", sidebar_id))?;
+
+    let (open, close) = match category {
+        None => (sidebar_open, sidebar_close),
+        Some(label) => {
+            let not_found = || format!("no category labelled `{}` in sidebar `{}`", label, sidebar_id);
+            // The outermost category with the label, should a nested one share it
+            let label_index = (sidebar_open..sidebar_close)
+                .filter(|&i| is_key(&tokens[i].0, "label") && tokens.get(i + 1).map(|t| &t.0) == Some(&Token::Punct(':')) && tokens.get(i + 2).map(|t| &t.0) == Some(&Token::Str(label.to_string())))
+                .min_by_key(|&i| depth_at(&tokens, sidebar_open, i))
+                .ok_or_else(not_found)?;
+            let object_open = enclosing_open(&tokens, label_index).ok_or_else(not_found)?;
+            let object_close = matching_close(&tokens, object_open).ok_or_else(not_found)?;
+            let items_open = find_key_array(&tokens, object_open + 1, object_close, "items", true).ok_or_else(|| format!("category `{}` has no `items` array", label))?;
+            let items_close = matching_close(&tokens, items_open).ok_or_else(|| format!("category `{}` is not closed", label))?;
+            (items_open, items_close)
+        }
+    };
+
+    let start = tokens[open].1;
+    let end = tokens[close].2;
+    // Continuation lines are indented like the line the array starts on
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let indent: String = source[line_start..].chars().take_while(|c| *c == ' ' || *c == '\t').collect();
+    let items = items.replace('\n', &format!("\n{}", indent));
+
+    Ok(format!("{}{}{}", &source[..start], items, &source[end..]))
+}
+
+fn is_key(token: &Token, name: &str) -> bool {
+    matches!(token, Token::Word(word) | Token::Str(word) if word == name)
+}
+
+// Index of the `[` in `name: [` between `from` and `to`, optionally only at the outermost level
+// of that range, e.g. a category's own `items` rather than those of a nested category
+fn find_key_array(tokens: &[(Token, usize, usize)], from: usize, to: usize, name: &str, top_level: bool) -> Option<usize> {
+    let mut depth = 0;
+    for i in from..to {
+        match tokens[i].0 {
+            Token::Punct('[' | '{' | '(') => depth += 1,
+            Token::Punct(']' | '}' | ')') => depth -= 1,
+            _ => {}
+        }
+        if (!top_level || depth == 0) && is_key(&tokens[i].0, name) && tokens.get(i + 1).map(|t| &t.0) == Some(&Token::Punct(':')) && tokens.get(i + 2).map(|t| &t.0) == Some(&Token::Punct('[')) {
+            return Some(i + 2);
+        }
+    }
+    None
+}
+
+// Brackets open at `index`, counted from `from`
+fn depth_at(tokens: &[(Token, usize, usize)], from: usize, index: usize) -> usize {
+    tokens[from..index].iter().fold(0, |depth, (token, _, _)| match token {
+        Token::Punct('[' | '{' | '(') => depth + 1,
+        Token::Punct(']' | '}' | ')') => depth.saturating_sub(1),
+        _ => depth,
+    })
+}
+
+fn matching_close(tokens: &[(Token, usize, usize)], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (i, (token, _, _)) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Punct('[' | '{' | '(') => depth += 1,
+            Token::Punct(']' | '}' | ')') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// The `{` of the object containing the token at `index`
+fn enclosing_open(tokens: &[(Token, usize, usize)], index: usize) -> Option<usize> {
+    let mut depth = 0;
+    for i in (0..index).rev() {
+        match tokens[i].0 {
+            Token::Punct(']' | '}' | ')') => depth += 1,
+            Token::Punct('{') if depth == 0 => return Some(i),
+            Token::Punct('[' | '{' | '(') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+// Tokens with their start and end byte offsets; comments and whitespace are skipped
+fn tokenize(source: &str) -> Vec<(Token, usize, usize)> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let offset = |i: usize| chars.get(i).map_or(source.len(), |(offset, _)| *offset);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i].1;
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i].1 != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i].1 == '*' && chars.get(i + 1).map(|(_, c)| *c) == Some('/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '\'' || c == '"' || c == '`' {
+            let mut content = String::new();
+            i += 1;
+            while i < chars.len() && chars[i].1 != c {
+                if chars[i].1 == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                }
+                content.push(chars[i].1);
+                i += 1;
+            }
+            i += 1;
+            tokens.push((Token::Str(content), offset(start), offset(i)));
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            while i < chars.len() && (chars[i].1.is_alphanumeric() || chars[i].1 == '_' || chars[i].1 == '$') {
+                i += 1;
+            }
+            tokens.push((Token::Word(source[offset(start)..offset(i)].to_string()), offset(start), offset(i)));
+        } else {
+            i += 1;
+            tokens.push((Token::Punct(c), offset(start), offset(i)));
+        }
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::patch_sidebar;
+
+    const ITEMS: &str = "[\n  \"api/index\"\n]";
+
+    #[test]
+    fn replaces_a_whole_sidebar() {
+        let source = "module.exports = {\n  api: ['old'],\n  other: ['kept'],\n};\n";
+        let patched = patch_sidebar(source, "api", None, ITEMS).unwrap();
+        assert_eq!(patched, "module.exports = {\n  api: [\n    \"api/index\"\n  ],\n  other: ['kept'],\n};\n");
+    }
+
+    #[test]
+    fn finds_labels_written_as_strings_or_words() {
+        for source in ["module.exports = {docs: [{label: 'API', items: ['old']}]};", "module.exports = {\"docs\": [{\"label\": \"API\", \"items\": [\"old\"]}]};"] {
+            let patched = patch_sidebar(source, "docs", Some("API"), "[]").unwrap();
+            assert!(patched.contains("items\": []") || patched.contains("items: []"), "{}", patched);
+            assert!(!patched.contains("old"), "{}", patched);
+        }
+    }
+
+    #[test]
+    fn patches_the_outer_category_when_a_nested_one_shares_its_label() {
+        let source = "module.exports = {docs: [{type: 'category', items: [{label: 'API', items: ['inner']}], label: 'API'}]};";
+        let patched = patch_sidebar(source, "docs", Some("API"), "[]").unwrap();
+        assert_eq!(patched, "module.exports = {docs: [{type: 'category', items: [], label: 'API'}]};");
+    }
+
+    #[test]
+    fn ignores_brackets_in_comments_and_strings() {
+        let source = "// docs: [ not this ]\nmodule.exports = {\n  /* ] } */\n  docs: ['a]', \"b[\", {label: 'API', items: ['old', `c}`]}],\n};\n";
+        let patched = patch_sidebar(source, "docs", Some("API"), "[]").unwrap();
+        assert_eq!(patched, "// docs: [ not this ]\nmodule.exports = {\n  /* ] } */\n  docs: ['a]', \"b[\", {label: 'API', items: []}],\n};\n");
+    }
+
+    #[test]
+    fn reports_a_missing_sidebar_or_category() {
+        let source = "module.exports = {docs: [{label: 'Guides', items: []}]};";
+        assert_eq!(patch_sidebar(source, "api", None, "[]").unwrap_err(), "no sidebar `api` in the file");
+        assert_eq!(patch_sidebar(source, "docs", Some("API"), "[]").unwrap_err(), "no category labelled `API` in sidebar `docs`");
+    }
+
+    #[test]
+    fn patching_twice_changes_nothing_more() {
+        let source = "const sidebars = {\n  docs: [\n    'intro',\n    {type: 'category', label: 'API', items: []},\n  ],\n};\nexport default sidebars;\n";
+        let once = patch_sidebar(source, "docs", Some("API"), ITEMS).unwrap();
+        let twice = patch_sidebar(&once, "docs", Some("API"), ITEMS).unwrap();
+        assert_eq!(once, twice);
+    }
+}
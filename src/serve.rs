@@ -0,0 +1,129 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path};
+
+// Listens on localhost only, the server is meant for previews
+pub fn bind(port: u16) -> std::io::Result<TcpListener> {
+    TcpListener::bind(("127.0.0.1", port))
+}
+
+// Minimal static file server for previewing generated output
+pub fn serve(root: &Path, listener: TcpListener) -> std::io::Result<()> {
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = handle_request(root, stream) {
+                    eprintln!("warning: failed to answer request: {}", e);
+                }
+            }
+            Err(e) => eprintln!("warning: failed to accept connection: {}", e),
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_request(root: &Path, mut stream: TcpStream) -> std::io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let request_path = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let request_path = percent_decode(request_path.split(['?', '#']).next().unwrap_or("/"));
+    let relative_path = Path::new(request_path.trim_start_matches('/'));
+
+    // Never serve anything outside the output directory
+    if relative_path.components().any(|component| !matches!(component, Component::Normal(_))) {
+        return respond(&mut stream, "403 Forbidden", "text/plain", b"Forbidden");
+    }
+
+    let mut file_path = root.join(relative_path);
+    if file_path.is_dir() {
+        if !file_path.join("index.html").is_file() {
+            let listing = directory_listing(&file_path, &request_path)?;
+            return respond(&mut stream, "200 OK", "text/html; charset=utf-8", listing.as_bytes());
+        }
+        file_path = file_path.join("index.html");
+    }
+
+    match fs::read(&file_path) {
+        Ok(body) => respond(&mut stream, "200 OK", content_type(&file_path), &body),
+        Err(_) => respond(&mut stream, "404 Not Found", "text/plain", b"Not Found"),
+    }
+}
+
+// Markdown output has no index pages of its own, so list the directory instead
+fn directory_listing(dir: &Path, request_path: &str) -> std::io::Result<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().is_dir() { format!("{}/", name) } else { name }
+        })
+        .collect();
+    names.sort();
+
+    let base = format!("{}/", request_path.trim_end_matches('/'));
+    let mut content = format!("<!DOCTYPE html>\n<html><body><h1>Index of {}</h1>\n<ul>\n", escape_html(&base));
+    for name in names {
+        content.push_str(&format!("<li><a href=\"{}{}\">{}</a></li>\n", percent_encode(&base), percent_encode(&name), escape_html(&name)));
+    }
+    content.push_str("</ul></body></html>\n");
+    Ok(content)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Keeps `/` and the characters URLs never need escaped, e.g. `my file.md` -> `my%20file.md`
+fn percent_encode(path: &str) -> String {
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3).and_then(|hex| std::str::from_utf8(hex).ok()).and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &[u8]) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+    stream.write_all(body)
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|s| s.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        Some("json") => "application/json",
+        Some("md") | Some("txt") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
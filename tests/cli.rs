@@ -0,0 +1,81 @@
+use std::fs;
+use std::process::{Command, Output};
+
+mod common;
+
+use common::{CORPUS, WORKSPACE};
+
+fn noirdoc(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_noirdoc")).args(args).output().expect("noirdoc runs")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn build_writes_to_the_out_directory() {
+    let out = common::scratch_path("cli_build");
+    let _ = fs::remove_dir_all(&out);
+    let output = noirdoc(&["build", CORPUS, "--out", out.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("Generated "), "{}", stdout(&output));
+    assert!(out.join("docs/tricky_types/lib.md").is_file());
+    assert!(out.join("inventory/tricky_types.json").is_file());
+}
+
+#[test]
+fn check_exit_status_tells_warnings_from_success() {
+    let output = noirdoc(&["check", CORPUS]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("Checked 1 crates: 1 files, "), "{}", stdout(&output));
+
+    // `Case` has no doc comment
+    let output = noirdoc(&["check", WORKSPACE]);
+    assert_eq!(output.status.code(), Some(3), "{}", stderr(&output));
+    assert!(stderr(&output).contains("warning[missing-docs]: missing documentation for struct `Case`"), "{}", stderr(&output));
+}
+
+#[test]
+fn invalid_arguments_exit_with_a_usage_error() {
+    let output = noirdoc(&["check", WORKSPACE, "--exclude", "src/[lib.nr"]);
+    assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+}
+
+#[test]
+fn invalid_configuration_exits_with_a_usage_error() {
+    let config = common::scratch_path("cli_bad_config.toml");
+    fs::write(&config, "[output]\ndocs_dir = \"/docs\"\n").unwrap();
+    let output = noirdoc(&["check", CORPUS, "--config", config.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+    assert!(stderr(&output).contains("invalid value for `output.docs_dir`"), "{}", stderr(&output));
+}
+
+#[test]
+fn machine_readable_diagnostics_own_stdout() {
+    let output = noirdoc(&["--message-format", "json", "check", WORKSPACE]);
+    assert_eq!(output.status.code(), Some(3));
+    for line in stdout(&output).lines() {
+        serde_json::from_str::<serde_json::Value>(line).unwrap_or_else(|e| panic!("{}: {}", e, line));
+    }
+    assert!(stderr(&output).starts_with("Checked "), "{}", stderr(&output));
+}
+
+#[test]
+fn clean_removes_only_generated_files() {
+    let out = common::scratch_path("cli_clean");
+    let _ = fs::remove_dir_all(&out);
+    let output = noirdoc(&["build", CORPUS, "--out", out.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    fs::write(out.join("docs/intro.md"), "# Hand-written\n").unwrap();
+
+    let output = noirdoc(&["clean", CORPUS, "--out", out.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(!out.join("docs/tricky_types").exists());
+    assert!(!out.join("inventory/tricky_types.json").exists());
+    assert!(out.join("docs/intro.md").is_file());
+}
@@ -0,0 +1,35 @@
+// Fixtures and helpers shared by the integration tests; each test crate uses only some of them
+#![allow(dead_code)]
+
+use noir_docs_generator::{Config, DocBuilder};
+use std::path::PathBuf;
+
+pub const CORPUS: &str = "tests/fixtures/tricky_types";
+pub const WORKSPACE: &str = "tests/fixtures/nested_crates";
+
+// The default configuration without lint warnings
+pub fn config() -> Config {
+    let mut config = Config::default();
+    config.features.lints = false;
+    config
+}
+
+// Every output file built from `input`, as its path and content
+pub fn render(input: &str, config: Config) -> Vec<(String, String)> {
+    let documentation = DocBuilder::new(config).input(input).build().expect("the fixture builds");
+    assert!(documentation.errors.is_empty(), "{:?}", documentation.errors);
+    documentation.files.into_iter().map(|file| (file.path.display().to_string(), file.content)).collect()
+}
+
+pub fn file<'a>(files: &'a [(String, String)], path: &str) -> Option<&'a str> {
+    files.iter().find(|(file_path, _)| file_path == path).map(|(_, content)| content.as_str())
+}
+
+pub fn page<'a>(files: &'a [(String, String)], path: &str) -> &'a str {
+    file(files, path).unwrap_or_else(|| panic!("no file {}", path))
+}
+
+// A file in the test's scratch directory, for outputs other runs read back
+pub fn scratch_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name)
+}
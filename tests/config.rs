@@ -0,0 +1,51 @@
+use noir_docs_generator::{Config, Error, Span};
+use std::fs;
+
+mod common;
+
+// Loads `content` as a config file and returns the error it fails with
+fn load_error(name: &str, content: &str) -> Error {
+    let path = common::scratch_path(name);
+    fs::write(&path, content).unwrap();
+    match Config::load(&path) {
+        Err(error) => error,
+        Ok(config) => panic!("expected {} to fail, got {:?}", name, config),
+    }
+}
+
+#[test]
+fn parse_errors_point_at_the_offending_value() {
+    let error = load_error("bad_value.toml", "[output]\nformat = \"docusaurus\"\nsidebar_mode = \"sideways\"\n");
+    match &error {
+        Error::Config { path, key, span, .. } => {
+            assert!(path.as_ref().is_some_and(|path| path.ends_with("bad_value.toml")));
+            assert_eq!(key.as_deref(), Some("output.sidebar_mode"));
+            assert_eq!(*span, Some(Span { line: 3, column: 16, length: 10 }));
+        }
+        other => panic!("expected a config error, got {:?}", other),
+    }
+    assert!(error.to_string().contains("bad_value.toml:3:16: invalid value for `output.sidebar_mode`: "), "{}", error);
+}
+
+#[test]
+fn unknown_tables_are_named() {
+    match load_error("bad_table.toml", "[output]\nformat = \"html\"\n\n[outptu]\ndir = \"site\"\n") {
+        Error::Config { key, span, .. } => {
+            assert_eq!(key.as_deref(), Some("outptu"));
+            assert_eq!(span.map(|span| span.line), Some(4));
+        }
+        other => panic!("expected a config error, got {:?}", other),
+    }
+}
+
+#[test]
+fn validation_errors_name_the_key_and_file() {
+    match load_error("bad_docs_dir.toml", "[output]\ndocs_dir = \"/docs\"\n") {
+        Error::Config { path, key, span, .. } => {
+            assert!(path.as_ref().is_some_and(|path| path.ends_with("bad_docs_dir.toml")));
+            assert_eq!(key.as_deref(), Some("output.docs_dir"));
+            assert_eq!(span, None);
+        }
+        other => panic!("expected a config error, got {:?}", other),
+    }
+}
@@ -0,0 +1,23 @@
+use noir_docs_generator::DocBuilder;
+
+mod common;
+
+use common::WORKSPACE;
+
+#[test]
+fn files_outside_src_are_named_from_their_crate() {
+    let documentation = DocBuilder::new(common::config()).input(WORKSPACE).build().expect("the workspace builds");
+    assert!(documentation.errors.is_empty(), "{:?}", documentation.errors);
+
+    let library = documentation.libraries.iter().find(|library| library.name == "aztec").expect("the nested crate");
+    let mut module_paths: Vec<String> = library.files.iter().map(|file| file.module_path.join("::")).collect();
+    module_paths.sort();
+    assert_eq!(module_paths, ["", "tests::t"]);
+
+    let paths: Vec<String> = documentation.files.iter().map(|file| file.path.display().to_string()).collect();
+    assert!(paths.contains(&"docs/aztec/tests/t.md".to_string()), "{:#?}", paths);
+    assert!(!paths.iter().any(|path| path.contains("aztec/aztec")), "{:#?}", paths);
+
+    let inventory = documentation.inventories.iter().find(|inventory| inventory.crate_name == "aztec").unwrap();
+    assert!(inventory.items.iter().any(|item| item.path == "aztec::tests::t::check"));
+}
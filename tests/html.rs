@@ -0,0 +1,98 @@
+use noir_docs_generator::config::PageMode;
+use noir_docs_generator::render::OutputFormat;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+mod common;
+
+use common::{page, CORPUS, WORKSPACE};
+
+fn render(input: &str, page_mode: PageMode) -> Vec<(String, String)> {
+    let mut config = common::config();
+    config.output.format = OutputFormat::Html;
+    config.output.page_mode = page_mode;
+    common::render(input, config)
+}
+
+// `href` resolved against the directory of `page`, without `.` and `..`
+fn resolve(page: &str, href: &str) -> PathBuf {
+    let mut path = PathBuf::new();
+    for component in Path::new(page).parent().unwrap().join(href).components() {
+        match component {
+            Component::ParentDir => assert!(path.pop(), "{} leaves the site from {}", href, page),
+            Component::Normal(part) => path.push(part),
+            _ => {}
+        }
+    }
+    path
+}
+
+fn hrefs(content: &str) -> Vec<String> {
+    let href_regex = Regex::new(r#"href="([^"]*)""#).unwrap();
+    href_regex.captures_iter(content).map(|captures| captures[1].to_string()).collect()
+}
+
+#[test]
+fn relative_links_resolve_from_every_page() {
+    for page_mode in [PageMode::Module, PageMode::Item] {
+        for input in [CORPUS, WORKSPACE] {
+            let files = render(input, page_mode);
+            let pages: HashMap<PathBuf, &str> = files.iter().map(|(path, content)| (PathBuf::from(path), content.as_str())).collect();
+            for (path, content) in files.iter().filter(|(path, _)| path.ends_with(".html")) {
+                // External links such as `https://noir-lang.org` in doc comments are left alone
+                for href in hrefs(content).into_iter().filter(|href| !href.contains("://")) {
+                    assert!(!href.starts_with('/'), "{} links {} from the root", path, href);
+                    let (target, anchor) = href.split_once('#').unwrap_or((&href, ""));
+                    let target_content = if target.is_empty() { content.as_str() } else { pages.get(&resolve(path, target)).unwrap_or_else(|| panic!("{} links to missing {}", path, href)) };
+                    if !anchor.is_empty() {
+                        assert!(target_content.contains(&format!("id=\"{}\"", anchor)), "{} links to missing anchor {}", path, href);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn stylesheet_is_found_at_every_depth() {
+    let files = render(WORKSPACE, PageMode::Item);
+    let mut depths = Vec::new();
+    for (path, content) in files.iter().filter(|(path, _)| path.ends_with(".html")) {
+        let depth = path.matches('/').count();
+        let stylesheet = format!("<link rel=\"stylesheet\" href=\"{}static/noirdoc.css\">", "../".repeat(depth));
+        assert!(content.contains(&stylesheet), "{} lacks {}", path, stylesheet);
+        depths.push(depth);
+    }
+    depths.sort();
+    depths.dedup();
+    assert_eq!(depths, [0, 1, 2, 3]);
+    assert!(files.iter().any(|(path, _)| path == "static/noirdoc.css"));
+}
+
+#[test]
+fn sidebar_lists_every_module() {
+    let files = render(WORKSPACE, PageMode::Module);
+    for path in ["index.html", "aztec/index.html", "aztec/lib.html", "aztec/tests/t.html"] {
+        let content = page(&files, path);
+        let sidebar = &content[content.find("<nav class=\"sidebar\">").unwrap()..content.find("</nav>").unwrap()];
+        for module in [">aztec</a>", ">aztec::tests::t</a>"] {
+            assert!(sidebar.contains(module), "{} lacks {} in {}", path, module, sidebar);
+        }
+    }
+    let content = page(&files, "aztec/tests/t.html");
+    assert!(content.contains("<a href=\"../../aztec/tests/t.html\" class=\"current\">aztec::tests::t</a>"), "{}", content);
+}
+
+#[test]
+fn signatures_highlight_keywords_and_primitives() {
+    let files = render(CORPUS, PageMode::Module);
+    let content = page(&files, "tricky_types/lib.html");
+    assert!(
+        content.contains("<span class=\"keyword\">const</span> <span class=\"type\">CAPACITY</span>: <span class=\"type\">u32</span> = <span class=\"number\">1</span> &lt;&lt; <span class=\"number\">4</span>"),
+        "{}",
+        content
+    );
+    assert!(content.contains("<span class=\"keyword\">fn</span> <span class=\"function\">insert</span>(&amp;<span class=\"keyword\">mut</span> <span class=\"keyword\">self</span>, "), "{}", content);
+    assert!(content.contains("<span class=\"type\">Field</span>"), "{}", content);
+}
@@ -0,0 +1,76 @@
+use noir_docs_generator::config::PageMode;
+
+mod common;
+
+use common::{page, render, WORKSPACE};
+
+fn render_pages(page_mode: PageMode) -> Vec<(String, String)> {
+    let mut config = common::config();
+    config.output.page_mode = page_mode;
+    render(WORKSPACE, config)
+}
+
+// The text of `content` from `heading` up to the next heading of the same level
+fn section<'a>(content: &'a str, heading: &str) -> &'a str {
+    let start = content.find(heading).unwrap_or_else(|| panic!("no {} in {}", heading, content));
+    let level = heading.split(' ').next().unwrap();
+    let rest = &content[start + heading.len()..];
+    let end = rest.find(&format!("\n{} ", level)).map_or(rest.len(), |end| end + 1);
+    &content[start..start + heading.len() + end]
+}
+
+#[test]
+fn trait_impls_are_headed_by_their_signature() {
+    let files = render(WORKSPACE, common::config());
+    let content = page(&files, "docs/aztec/lib.md");
+    assert!(content.contains("### `impl Serialize for Note` {#impl-Note}\n"), "{}", content);
+    assert!(content.contains("### `impl Compress for Note` {#impl-Note-2}\n"), "{}", content);
+}
+
+#[test]
+fn inherent_impls_are_listed_with_their_struct() {
+    let files = render_pages(PageMode::Module);
+    let note = section(page(&files, "docs/aztec/lib.md"), "### Note {#struct-Note}");
+    assert!(note.contains("#### `impl Note` {#impl-Note-3}\n\nDefined in `aztec::tests::t`.\n\nTest helpers for notes.\n"), "{}", note);
+    assert!(note.contains("##### `is_owned_by` {#method-Note-is_owned_by}\n"), "{}", note);
+
+    // The module the block is written in does not list it again
+    let module = page(&files, "docs/aztec/tests/t.md");
+    assert!(!module.contains("is_owned_by"), "{}", module);
+}
+
+#[test]
+fn inherent_impls_are_listed_on_struct_pages() {
+    let files = render_pages(PageMode::Item);
+    let note = page(&files, "docs/aztec/lib/struct-Note.md");
+    assert!(note.contains("## Implementations\n\n### `impl Note` {#impl-Note}\n\nDefined in `aztec::tests::t`.\n"), "{}", note);
+    assert!(note.contains("#### `is_owned_by` {#method-Note-is_owned_by}\n"), "{}", note);
+    assert!(!page(&files, "docs/aztec/tests/t.md").contains("is_owned_by"));
+}
+
+#[test]
+fn traits_list_their_implementors() {
+    let files = render_pages(PageMode::Module);
+    let content = page(&files, "docs/aztec/lib.md");
+    let serialize = section(content, "### Serialize {#trait-Serialize}");
+    assert!(serialize.contains("Implementors:\n- [`Note`](../aztec/lib.md#struct-Note)\n- [`Case`](../aztec/tests/t.md#struct-Case) (derived)\n\n"), "{}", serialize);
+    let compress = section(content, "### Compress {#trait-Compress}");
+    assert!(compress.contains("Implementors:\n- [`Note`](../aztec/lib.md#struct-Note)\n\n"), "{}", compress);
+
+    let files = render_pages(PageMode::Item);
+    let serialize = page(&files, "docs/aztec/lib/trait-Serialize.md");
+    assert!(serialize.contains("Implementors:\n- [`Note`](../../aztec/lib/struct-Note.md)\n- [`Case`](../../aztec/tests/t/struct-Case.md) (derived)\n"), "{}", serialize);
+}
+
+#[test]
+fn types_list_the_traits_they_implement() {
+    let files = render_pages(PageMode::Module);
+    let note = section(page(&files, "docs/aztec/lib.md"), "### Note {#struct-Note}");
+    assert!(note.contains("Implemented traits:\n- [`Serialize`](../aztec/lib.md#trait-Serialize)\n- [`Compress`](../aztec/lib.md#trait-Compress)\n\n"), "{}", note);
+    let case = section(page(&files, "docs/aztec/tests/t.md"), "### Case {#struct-Case}");
+    assert!(case.contains("Implemented traits:\n- [`Serialize`](../../aztec/lib.md#trait-Serialize) (derived)\n"), "{}", case);
+
+    // Structs without trait impls have no list
+    let hidden = section(page(&files, "docs/aztec/lib.md"), "### Hidden {#struct-Hidden}");
+    assert!(!hidden.contains("Implemented traits:"), "{}", hidden);
+}
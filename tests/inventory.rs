@@ -0,0 +1,40 @@
+use noir_docs_generator::config::ExternConfig;
+use noir_docs_generator::{Config, DocBuilder};
+use std::fs;
+
+mod common;
+
+use common::{config, CORPUS, WORKSPACE};
+
+#[test]
+fn modules_are_listed_and_linked_from_other_runs() {
+    let documentation = DocBuilder::new(config()).input(CORPUS).build().expect("the corpus builds");
+    let inventory = &documentation.inventories[0];
+    let module = inventory.items.iter().find(|item| item.path == "tricky_types").expect("an entry for the crate root module");
+    assert_eq!(module.kind, "module");
+    assert_eq!(module.page, "tricky_types/lib");
+    assert_eq!(module.anchor, None);
+
+    let inventory_path = common::scratch_path("tricky_types.json");
+    fs::write(&inventory_path, serde_json::to_string(inventory).unwrap()).unwrap();
+
+    let mut config = Config::default();
+    config.externs.push(ExternConfig {
+        base_url: "https://example.com/tricky".to_string(),
+        inventory: inventory_path,
+    });
+    let documentation = DocBuilder::new(config).input(WORKSPACE).build().expect("the workspace builds");
+    assert!(documentation.errors.is_empty(), "{:?}", documentation.errors);
+    assert!(!documentation.warnings.iter().any(|warning| warning.code == "unresolved-link"), "{:#?}", documentation.warnings);
+
+    let root_page = documentation.files.iter().find(|file| file.path.ends_with("aztec/lib.md")).expect("the crate root page");
+    assert!(root_page.content.contains("[`tricky_types`](https://example.com/tricky/docs/tricky_types/lib)"), "{}", root_page.content);
+}
+
+#[test]
+fn trait_impl_methods_are_keyed_by_trait() {
+    let documentation = DocBuilder::new(config()).input(WORKSPACE).build().expect("the workspace builds");
+    let inventory = documentation.inventories.iter().find(|inventory| inventory.crate_name == "aztec").unwrap();
+    let methods: Vec<&str> = inventory.items.iter().filter(|item| item.kind == "method").map(|item| item.path.as_str()).collect();
+    assert_eq!(methods, ["aztec::Note::Serialize::pack", "aztec::Note::Compress::pack", "aztec::Note::is_owned_by", "aztec::Note::same_owner"]);
+}
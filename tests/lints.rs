@@ -0,0 +1,37 @@
+use noir_docs_generator::{Config, DocBuilder};
+
+mod common;
+
+use common::WORKSPACE;
+
+const LINT_CASES: &str = "tests/fixtures/lint_cases";
+
+fn warnings(input: &str, code: &str) -> Vec<String> {
+    let documentation = DocBuilder::new(Config::default()).input(input).build().expect("the input builds");
+    documentation.warnings.into_iter().filter(|warning| warning.code == code).map(|warning| warning.message).collect()
+}
+
+#[test]
+fn private_types_need_no_docs() {
+    let documentation = DocBuilder::new(Config::default()).input(WORKSPACE).build().expect("the workspace builds");
+    let missing: Vec<&str> = documentation.warnings.iter().filter(|warning| warning.code == "missing-docs").map(|warning| warning.message.as_str()).collect();
+    assert!(!missing.iter().any(|message| message.contains("`Hidden`")), "{:#?}", missing);
+    assert!(missing.contains(&"missing documentation for struct `Case`"), "{:#?}", missing);
+}
+
+#[test]
+fn methods_of_private_traits_and_types_need_no_docs() {
+    assert_eq!(warnings(LINT_CASES, "missing-docs"), ["missing documentation for function `undocumented`"]);
+}
+
+#[test]
+fn module_docs_links_must_resolve() {
+    let documentation = DocBuilder::new(Config::default()).input(LINT_CASES).build().expect("the fixture builds");
+    let warning = documentation.warnings.iter().find(|warning| warning.code == "unresolved-link").expect("an unresolved-link warning");
+    assert_eq!(warning.message, "unresolved link to `Nowhere` in the documentation of `lint_cases`");
+    assert!(warning.path.as_ref().is_some_and(|path| path.ends_with("lint_cases/src/lib.nr")));
+    assert_eq!(warning.span, None);
+
+    // The workspace's crate root links to `tricky_types`, which only an inventory documents
+    assert_eq!(warnings(WORKSPACE, "unresolved-link"), ["unresolved link to `tricky_types` in the documentation of `aztec`"]);
+}
@@ -0,0 +1,32 @@
+use noir_docs_generator::render::OutputFormat;
+
+mod common;
+
+use common::WORKSPACE;
+
+fn digest() -> String {
+    let mut config = common::config();
+    config.output.format = OutputFormat::LlmsTxt;
+    common::render(WORKSPACE, config).into_iter().find(|(path, _)| path.ends_with("llms/aztec.txt")).expect("the crate digest").1
+}
+
+#[test]
+fn lists_only_the_public_api() {
+    let digest = digest();
+    assert!(!digest.contains("Hidden"), "{}", digest);
+    assert!(digest.contains("- aztec::Wrapper: `struct Wrapper<T> { inner: T, .. }` - Wraps a value with a secret.\n"), "{}", digest);
+    assert!(!digest.contains("secret:"), "{}", digest);
+}
+
+#[test]
+fn lists_trait_impl_methods_under_their_trait() {
+    let digest = digest();
+    assert!(digest.contains("- aztec::Note::Serialize::pack: `fn pack(self) -> Field`\n"), "{}", digest);
+    assert!(digest.contains("- aztec::Note::Compress::pack: `fn pack(self) -> Field`\n"), "{}", digest);
+}
+
+#[test]
+fn signatures_keep_the_receiver() {
+    let digest = digest();
+    assert!(digest.contains("- aztec::Note::is_owned_by: `fn is_owned_by(self, owner: Field) -> bool` - Whether the note belongs to `owner`.\n"), "{}", digest);
+}
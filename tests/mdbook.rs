@@ -0,0 +1,40 @@
+use noir_docs_generator::render::OutputFormat;
+use std::path::PathBuf;
+
+mod common;
+
+use common::page;
+
+const MODULE_TREE: &str = "tests/fixtures/module_tree";
+
+fn render(docs_dir: &str) -> Vec<(String, String)> {
+    let mut config = common::config();
+    config.output.format = OutputFormat::MdBook;
+    config.output.docs_dir = PathBuf::from(docs_dir);
+    common::render(MODULE_TREE, config)
+}
+
+#[test]
+fn summary_nests_modules_under_their_crate() {
+    let files = render("docs");
+    assert_eq!(
+        page(&files, "docs/SUMMARY.md"),
+        "# Summary\n\n\
+         [Aztec.nr Overview](aztec-nr.md)\n\n\
+         - [module_tree](module_tree/index.md)\n  \
+           - [module_tree](module_tree/lib.md)\n  \
+           - [module_tree::utils](module_tree/utils.md)\n  \
+           - [module_tree::utils::mod2](module_tree/utils/mod2.md)\n"
+    );
+    // Every chapter in the summary is written
+    for chapter in ["aztec-nr.md", "module_tree/index.md", "module_tree/lib.md", "module_tree/utils.md", "module_tree/utils/mod2.md"] {
+        page(&files, &format!("docs/{}", chapter));
+    }
+}
+
+#[test]
+fn book_toml_points_at_the_docs_dir() {
+    let files = render("content/api");
+    assert_eq!(page(&files, "book.toml"), "[book]\ntitle = \"Aztec.nr Project\"\nsrc = \"content/api\"\n");
+    page(&files, "content/api/SUMMARY.md");
+}
@@ -0,0 +1,125 @@
+use noir_docs_generator::render::escape::{escape_heading, escape_markdown, escape_table_cell};
+use noir_docs_generator::config::PageMode;
+use noir_docs_generator::render::OutputFormat;
+
+mod common;
+
+use common::CORPUS;
+
+fn render(format: OutputFormat) -> Vec<(String, String)> {
+    render_pages(format, PageMode::Module)
+}
+
+fn render_pages(format: OutputFormat, page_mode: PageMode) -> Vec<(String, String)> {
+    let mut config = common::config();
+    config.output.format = format;
+    config.output.page_mode = page_mode;
+    common::render(CORPUS, config).into_iter().filter(|(path, _)| path.ends_with(".md")).collect()
+}
+
+// Lines with a `<`, `{` or `}` that MDX would parse as JSX or an expression
+fn unescaped_lines(markdown: &str) -> Vec<String> {
+    let mut offending = Vec::new();
+    let mut in_fence = false;
+    let mut in_frontmatter = markdown.starts_with("---\n");
+
+    for (index, line) in markdown.lines().enumerate() {
+        if in_frontmatter {
+            in_frontmatter = index == 0 || line != "---";
+            continue;
+        }
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut text = strip_code_spans(line);
+        // Explicit heading IDs are supported by Docusaurus
+        if text.starts_with('#') {
+            if let Some(start) = text.rfind(" {#") {
+                text.truncate(start);
+            }
+        }
+        let text = text.replace("\\<", "").replace("\\{", "").replace("\\}", "").replace("<https://", "");
+        if text.contains(['<', '{', '}']) {
+            offending.push(line.to_string());
+        }
+    }
+
+    offending
+}
+
+fn strip_code_spans(line: &str) -> String {
+    let mut text = String::new();
+    let mut in_code = false;
+    for c in line.chars() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if !in_code {
+            text.push(c);
+        }
+    }
+    text
+}
+
+#[test]
+fn escapes_mdx_syntax_in_prose() {
+    assert_eq!(escape_markdown("stored as BoundedVec<(K, V), 16>"), "stored as BoundedVec\\<(K, V), 16>");
+    assert_eq!(escape_markdown("pairs like { key: value }"), "pairs like \\{ key: value \\}");
+    assert_eq!(escape_markdown("already \\{ escaped \\}"), "already \\{ escaped \\}");
+    assert_eq!(escape_markdown("see <https://noir-lang.org>"), "see <https://noir-lang.org>");
+}
+
+#[test]
+fn leaves_code_untouched() {
+    assert_eq!(escape_markdown("use `BoundedVec<Field, 4>` {here}"), "use `BoundedVec<Field, 4>` \\{here\\}");
+    assert_eq!(escape_markdown("``a ` <b>`` <c>"), "``a ` <b>`` \\<c>");
+    let block = "Example:\n\n```noir\nlet x: BoundedVec<Field, 4> = {};\n```\n<after>";
+    assert_eq!(escape_markdown(block), "Example:\n\n```noir\nlet x: BoundedVec<Field, 4> = {};\n```\n\\<after>");
+}
+
+#[test]
+fn escapes_headings_and_table_cells() {
+    assert_eq!(escape_heading("Impl for Storage < K , V >"), "Impl for Storage \\< K , V >");
+    assert_eq!(escape_table_cell("either a | b,\nor a <V>"), "either a \\| b, or a \\<V>");
+    assert_eq!(escape_table_cell("`a | b`"), "`a \\| b`");
+}
+
+#[test]
+fn docusaurus_pages_are_mdx_safe() {
+    for (path, content) in render(OutputFormat::Docusaurus) {
+        let offending = unescaped_lines(&content);
+        assert!(offending.is_empty(), "{} has unescaped MDX syntax: {:#?}", path, offending);
+    }
+}
+
+#[test]
+fn item_pages_are_mdx_safe() {
+    let pages = render_pages(OutputFormat::Docusaurus, PageMode::Item);
+    assert!(pages.iter().any(|(path, _)| path.ends_with("tricky_types/lib/constant-CAPACITY.md")));
+    for (path, content) in pages {
+        let offending = unescaped_lines(&content);
+        assert!(offending.is_empty(), "{} has unescaped MDX syntax: {:#?}", path, offending);
+    }
+}
+
+#[test]
+fn mdbook_chapters_are_escaped() {
+    for (path, content) in render(OutputFormat::MdBook) {
+        let offending = unescaped_lines(&content);
+        assert!(offending.is_empty(), "{} has unescaped Markdown: {:#?}", path, offending);
+    }
+}
+
+#[test]
+fn parameter_tables_keep_their_columns() {
+    let pages = render(OutputFormat::Docusaurus);
+    let (_, content) = pages.iter().find(|(path, _)| path.ends_with("tricky_types/lib.md")).expect("the crate root page");
+    for row in content.lines().filter(|line| line.starts_with("| `")) {
+        let columns = row.replace("\\|", "").matches('|').count();
+        assert_eq!(columns, 4, "row split into extra columns: {}", row);
+    }
+}
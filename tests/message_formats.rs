@@ -0,0 +1,74 @@
+use noir_docs_generator::diagnostics::{format_diagnostics, MessageFormat};
+use noir_docs_generator::{Config, Diagnostic, DocBuilder, Error, Span};
+use serde_json::Value;
+use std::path::PathBuf;
+
+mod common;
+
+use common::WORKSPACE;
+
+// The missing-docs warning for the undocumented `Case` struct
+fn missing_docs() -> Vec<Diagnostic> {
+    let documentation = DocBuilder::new(Config::default()).input(WORKSPACE).build().expect("the workspace builds");
+    let warnings: Vec<Diagnostic> = documentation.warnings.into_iter().filter(|warning| warning.message == "missing documentation for struct `Case`").collect();
+    assert_eq!(warnings.len(), 1);
+    warnings
+}
+
+fn parse_error() -> Diagnostic {
+    Diagnostic::from(&Error::Parse {
+        path: PathBuf::from("aztec/src/broken.nr"),
+        message: "expected `)`".to_string(),
+        span: Span { line: 3, column: 15, length: 1 },
+    })
+}
+
+#[test]
+fn json_writes_one_object_per_line() {
+    let mut diagnostics = missing_docs();
+    diagnostics.push(parse_error());
+    let output = format_diagnostics(&diagnostics, MessageFormat::Json);
+    let lines: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).expect("a JSON object")).collect();
+    assert_eq!(lines.len(), 2);
+
+    assert_eq!(lines[0]["severity"], "warning");
+    assert_eq!(lines[0]["code"], "missing-docs");
+    assert!(lines[0]["path"].as_str().is_some_and(|path| path.ends_with("aztec/tests/t.nr")), "{}", lines[0]);
+    assert_eq!(lines[0]["span"]["line"], 9);
+
+    assert_eq!(lines[1]["severity"], "error");
+    assert_eq!(lines[1]["code"], "parse-error");
+    assert_eq!(lines[1]["span"], serde_json::json!({ "line": 3, "column": 15, "length": 1 }));
+    assert!(lines[1]["help"].as_str().is_some_and(|help| help.contains("noirdoc: skip")));
+}
+
+#[test]
+fn sarif_writes_one_log_with_rules_and_locations() {
+    let mut diagnostics = missing_docs();
+    diagnostics.push(parse_error());
+    let log: Value = serde_json::from_str(&format_diagnostics(&diagnostics, MessageFormat::Sarif)).expect("a SARIF log");
+    assert_eq!(log["version"], "2.1.0");
+
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "noirdoc");
+    assert_eq!(run["tool"]["driver"]["rules"], serde_json::json!([{ "id": "missing-docs" }, { "id": "parse-error" }]));
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results[0]["ruleId"], "missing-docs");
+    assert_eq!(results[0]["level"], "warning");
+    assert_eq!(results[0]["locations"][0]["physicalLocation"]["region"]["startLine"], 9);
+
+    assert_eq!(results[1]["ruleId"], "parse-error");
+    assert_eq!(results[1]["level"], "error");
+    let location = &results[1]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "aztec/src/broken.nr");
+    assert_eq!(location["region"], serde_json::json!({ "startLine": 3, "startColumn": 15, "endColumn": 16 }));
+    assert!(results[1]["message"]["text"].as_str().is_some_and(|text| text.starts_with("expected `)`\nhelp: ")));
+}
+
+#[test]
+fn empty_sarif_logs_are_still_valid() {
+    let log: Value = serde_json::from_str(&format_diagnostics(&[], MessageFormat::Sarif)).expect("a SARIF log");
+    assert_eq!(log["runs"][0]["results"], serde_json::json!([]));
+    assert_eq!(format_diagnostics(&[], MessageFormat::Json), "");
+}
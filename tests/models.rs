@@ -0,0 +1,154 @@
+use noir_docs_generator::render::OutputFormat;
+use noir_docs_generator::{Config, DocBuilder, Error};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+mod common;
+
+use common::{page, CORPUS, WORKSPACE};
+
+// Exports the model of `input` to `<name>.json` in the test's scratch directory
+fn export_model(input: &str, name: &str, repository_url: Option<&str>) -> PathBuf {
+    let mut config = common::config();
+    config.output.format = OutputFormat::Json;
+    config.site.repository_url = repository_url.map(str::to_string);
+    let (_, model) = common::render(input, config).into_iter().find(|(path, _)| path.ends_with("model.json")).expect("a model file");
+
+    let path = common::scratch_path(&format!("{}.json", name));
+    fs::write(&path, model).unwrap();
+    path
+}
+
+// Checks `value` against the subset of JSON Schema the model schema uses, treating properties the
+// schema does not list as errors so that the two cannot drift apart
+fn check_schema(value: &Value, schema: &Value, defs: &Value, at: &str) {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let name = reference.trim_start_matches("#/$defs/");
+        return check_schema(value, &defs[name], defs, at);
+    }
+    if !schema["const"].is_null() {
+        assert_eq!(value, &schema["const"], "{}", at);
+    }
+    if let Some(variants) = schema["enum"].as_array() {
+        assert!(variants.contains(value), "{}: {} is not one of {:?}", at, value, variants);
+    }
+    let types: Vec<&str> = match &schema["type"] {
+        Value::String(ty) => vec![ty.as_str()],
+        Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    let type_name = match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_u64() || number.is_i64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    };
+    assert!(types.is_empty() || types.contains(&type_name), "{}: expected {:?}, got {}", at, types, value);
+
+    match value {
+        Value::Object(object) => {
+            for required in schema["required"].as_array().into_iter().flatten() {
+                assert!(object.contains_key(required.as_str().unwrap()), "{}: missing {}", at, required);
+            }
+            for (key, property) in object {
+                let property_schema = &schema["properties"][key];
+                assert!(!property_schema.is_null(), "{}: {} is not in the schema", at, key);
+                check_schema(property, property_schema, defs, &format!("{}.{}", at, key));
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                check_schema(item, &schema["items"], defs, &format!("{}[{}]", at, index));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn exported_models_match_the_schema() {
+    let schema: Value = serde_json::from_str(&fs::read_to_string("schema/model.schema.json").unwrap()).unwrap();
+    for (input, name) in [(CORPUS, "tricky_schema"), (WORKSPACE, "nested_schema")] {
+        let model: Value = serde_json::from_str(&fs::read_to_string(export_model(input, name, Some("https://example.com/blob/main"))).unwrap()).unwrap();
+        check_schema(&model, &schema, &schema["$defs"], name);
+    }
+}
+
+#[test]
+fn methods_keep_their_receiver() {
+    let model: Value = serde_json::from_str(&fs::read_to_string(export_model(CORPUS, "tricky_receivers", None)).unwrap()).unwrap();
+    let module = &model["crates"][0]["modules"][0];
+    assert_eq!(module["impls"][0]["methods"][0]["receiver"], "&mut self");
+    assert_eq!(module["traits"][0]["methods"][0]["receiver"], "self");
+    assert_eq!(module["functions"][0]["receiver"], Value::Null);
+
+    let signature = |path: &str| module["items"].as_array().unwrap().iter().find(|item| item["path"] == path).unwrap_or_else(|| panic!("no item {}", path))["signature"].clone();
+    assert_eq!(signature("tricky_types::Storage::insert"), "fn insert(&mut self, key: K, value: V) -> Option < V >");
+    assert_eq!(signature("tricky_types::Packable::pack"), "fn pack(self) -> [Field ; N]");
+}
+
+#[test]
+fn models_round_trip() {
+    let exported = export_model(WORKSPACE, "nested_round_trip", None);
+    let mut config = common::config();
+    config.output.format = OutputFormat::Json;
+    let documentation = DocBuilder::new(config).model(&exported).build().expect("the model loads");
+    assert!(documentation.errors.is_empty(), "{:?}", documentation.errors);
+    let reexported = documentation.files.into_iter().find(|file| file.path.ends_with("model.json")).expect("a model file");
+    assert_eq!(reexported.content, fs::read_to_string(&exported).unwrap());
+}
+
+#[test]
+fn models_render_like_their_sources() {
+    for (input, name) in [(CORPUS, "tricky_pages"), (WORKSPACE, "nested_pages")] {
+        for format in [OutputFormat::Docusaurus, OutputFormat::LlmsTxt] {
+            let exported = export_model(input, name, Some("https://example.com/blob/main"));
+            let config = || {
+                let mut config = common::config();
+                config.output.format = format;
+                config.site.repository_url = Some("https://example.com/blob/main".to_string());
+                config
+            };
+            let documentation = DocBuilder::new(config()).model(&exported).build().expect("the model renders");
+            assert!(documentation.errors.is_empty(), "{:?}", documentation.errors);
+            let from_model: Vec<(String, String)> = documentation.files.into_iter().map(|file| (file.path.display().to_string(), file.content)).collect();
+            assert_eq!(from_model, common::render(input, config()), "{:?} of {}", format, input);
+        }
+    }
+}
+
+#[test]
+fn source_links_point_to_each_model_repository() {
+    let tricky = export_model(CORPUS, "tricky_repo", Some("https://example.com/tricky/blob/main"));
+    let nested = export_model(WORKSPACE, "nested_repo", Some("https://example.com/nested/blob/main"));
+
+    let mut config = Config::default();
+    config.site.repository_url = Some("https://example.com/site/blob/main".to_string());
+    config.features.lints = false;
+    let documentation = DocBuilder::new(config).model(tricky).model(nested).build().expect("the models render");
+    assert!(documentation.errors.is_empty(), "{:?}", documentation.errors);
+    let files: Vec<(String, String)> = documentation.files.into_iter().map(|file| (file.path.display().to_string(), file.content)).collect();
+
+    let tricky_page = page(&files, "docs/tricky_types/lib.md");
+    assert!(tricky_page.contains("custom_edit_url: \"https://example.com/tricky/blob/main/src/lib.nr\""), "{}", tricky_page);
+    let nested_page = page(&files, "docs/aztec/tests/t.md");
+    assert!(nested_page.contains("[View source](https://example.com/nested/blob/main/aztec/tests/t.nr)"), "{}", nested_page);
+    assert!(!files.iter().any(|(_, content)| content.contains("example.com/site")));
+}
+
+#[test]
+fn crates_from_different_repositories_are_not_merged() {
+    let first = export_model(WORKSPACE, "nested_first_repo", Some("https://example.com/first/blob/main"));
+    let second = export_model(WORKSPACE, "nested_second_repo", Some("https://example.com/second/blob/main"));
+    match DocBuilder::new(common::config()).model(&first).model(&second).build() {
+        Err(Error::Config { message, .. }) => {
+            assert!(message.starts_with("crate `aztec` comes from both "), "{}", message);
+            assert!(message.contains("(https://example.com/first/blob/main)") && message.contains("(https://example.com/second/blob/main)"), "{}", message);
+        }
+        other => panic!("expected a config error, got {:?}", other.map(|documentation| documentation.files.len())),
+    }
+}
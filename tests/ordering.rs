@@ -0,0 +1,66 @@
+use noir_docs_generator::render::OutputFormat;
+use noir_docs_generator::{DocBuilder, OrderStrategy};
+
+mod common;
+
+use common::{page, CORPUS, WORKSPACE};
+
+// The `###` headings of the crate root page of `aztec`
+fn headings(order: OrderStrategy) -> Vec<String> {
+    let mut config = common::config();
+    config.source.order = order;
+    let files = common::render(WORKSPACE, config);
+    page(&files, "docs/aztec/lib.md").lines().filter_map(|line| line.strip_prefix("### ")).map(|heading| heading.split(" {#").next().unwrap().to_string()).collect()
+}
+
+#[test]
+fn repeated_builds_are_identical() {
+    for format in [OutputFormat::Docusaurus, OutputFormat::Json, OutputFormat::LlmsTxt] {
+        for input in [CORPUS, WORKSPACE] {
+            let render = || {
+                let mut config = common::config();
+                config.output.format = format;
+                common::render(input, config)
+            };
+            assert_eq!(render(), render(), "{:?} of {}", format, input);
+        }
+    }
+}
+
+#[test]
+fn source_order_keeps_declarations_in_place() {
+    assert_eq!(headings(OrderStrategy::Source), ["Note", "Wrapper", "Hidden", "Serialize", "Compress", "`impl Serialize for Note`", "`impl Compress for Note`"]);
+}
+
+#[test]
+fn alphabetical_order_interleaves_kinds() {
+    assert_eq!(headings(OrderStrategy::Alphabetical), ["Compress", "Hidden", "Note", "Serialize", "Wrapper", "`impl Serialize for Note`", "`impl Compress for Note`"]);
+}
+
+#[test]
+fn kind_then_name_order_sorts_within_kinds() {
+    assert_eq!(headings(OrderStrategy::KindThenName), ["Hidden", "Note", "Wrapper", "Compress", "Serialize", "`impl Serialize for Note`", "`impl Compress for Note`"]);
+}
+
+#[test]
+fn inventories_follow_the_order() {
+    let mut config = common::config();
+    config.source.order = OrderStrategy::Alphabetical;
+    let documentation = DocBuilder::new(config).input(WORKSPACE).build().expect("the workspace builds");
+    let inventory = documentation.inventories.iter().find(|inventory| inventory.crate_name == "aztec").unwrap();
+    let paths: Vec<&str> = inventory.items.iter().map(|item| item.path.as_str()).collect();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(paths, sorted);
+}
+
+#[test]
+fn parent_modules_precede_their_children() {
+    for order in [OrderStrategy::Source, OrderStrategy::Alphabetical, OrderStrategy::KindThenName] {
+        let mut config = common::config();
+        config.source.order = order;
+        let (libraries, _) = DocBuilder::new(config).input("tests/fixtures/module_tree").parse().expect("the crate parses");
+        let modules: Vec<String> = libraries[0].files.iter().map(|file| file.module_path.join("::")).collect();
+        assert_eq!(modules, ["", "utils", "utils::mod2"], "{:?}", order);
+    }
+}
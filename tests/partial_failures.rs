@@ -0,0 +1,34 @@
+use noir_docs_generator::{DocBuilder, Error, Span};
+use std::process::Command;
+
+mod common;
+
+const PARTIAL_FAILURE: &str = "tests/fixtures/partial_failure";
+
+#[test]
+fn files_that_fail_to_parse_leave_the_rest_documented() {
+    let documentation = DocBuilder::new(common::config()).input(PARTIAL_FAILURE).build().expect("the crate builds");
+    match documentation.errors.as_slice() {
+        [Error::Parse { path, span, .. }] => {
+            assert!(path.ends_with("partial_failure/src/broken.nr"), "{}", path.display());
+            assert_eq!(*span, Span { line: 4, column: 14, length: 1 });
+        }
+        errors => panic!("expected one parse error, got {:?}", errors),
+    }
+
+    let paths: Vec<String> = documentation.files.iter().map(|file| file.path.display().to_string()).collect();
+    assert!(paths.contains(&"docs/partial_failure/lib.md".to_string()), "{:#?}", paths);
+    assert!(paths.contains(&"docs/partial_failure/sibling.md".to_string()), "{:#?}", paths);
+    assert!(!paths.iter().any(|path| path.contains("broken")), "{:#?}", paths);
+}
+
+#[test]
+fn the_cli_summarises_the_errors() {
+    let out = common::scratch_path("partial_failure");
+    let output = Command::new(env!("CARGO_BIN_EXE_noirdoc")).args(["build", PARTIAL_FAILURE, "--out", out.to_str().unwrap()]).output().expect("noirdoc runs");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(stderr.contains("error[parse-error]: "), "{}", stderr);
+    assert!(stderr.ends_with("\n1 error, 0 warnings (1 parse-error)\n"), "{}", stderr);
+    assert!(out.join("docs/partial_failure/sibling.md").is_file());
+}
@@ -0,0 +1,126 @@
+use noir_docs_generator::config::PageMode;
+use noir_docs_generator::render::docusaurus::{SidebarFormat, SidebarMode};
+use noir_docs_generator::Config;
+use serde_json::Value;
+
+mod common;
+
+use common::{file, CORPUS};
+
+fn render(config: Config) -> Vec<(String, String)> {
+    common::render(CORPUS, config)
+}
+
+// The sidebar's category for `module_tree`, whose `utils` module has a `utils::mod2` child
+fn module_tree_category(config: Config) -> Value {
+    let files = common::render("tests/fixtures/module_tree", config);
+    let sidebars = file(&files, "sidebars.js").expect("a sidebars file");
+    let json = sidebars.strip_prefix("module.exports = ").and_then(|json| json.strip_suffix(";\n")).expect("a CommonJS module");
+    let sidebars: Value = serde_json::from_str(json).expect("the sidebars are JSON");
+    sidebars["someSidebar"][1].clone()
+}
+
+fn labels(category: &Value) -> Vec<&str> {
+    category["items"].as_array().expect("category items").iter().map(|item| item["label"].as_str().unwrap()).collect()
+}
+
+#[test]
+fn quoted_labels_round_trip() {
+    let label = r#"It's the "API""#;
+    let mut config = Config::default();
+    config.output.sidebar_id = "api-sidebar".to_string();
+    config.site.overview_label = label.to_string();
+    config.validate().expect("quoted sidebar IDs are valid");
+
+    let files = render(config);
+    let sidebars = file(&files, "sidebars.js").expect("a sidebars file");
+    let json = sidebars.strip_prefix("module.exports = ").and_then(|json| json.strip_suffix(";\n")).expect("a CommonJS module");
+    let sidebars: Value = serde_json::from_str(json).expect("the sidebars are JSON");
+    assert_eq!(sidebars["api-sidebar"][0]["label"], label);
+}
+
+#[test]
+fn autogenerated_categories_open_their_folder_index() {
+    let mut config = Config::default();
+    config.output.sidebar_format = SidebarFormat::Autogenerated;
+    config.output.page_mode = PageMode::Item;
+    let files = render(config);
+
+    assert!(file(&files, "docs/tricky_types/lib.md").is_none(), "the module page is also listed beside its folder");
+    let page = file(&files, "docs/tricky_types/lib/index.md").expect("the module page inside its folder");
+    assert!(page.contains("slug: \"/tricky_types/lib/index\""));
+
+    let category: Value = serde_json::from_str(file(&files, "docs/tricky_types/lib/_category_.json").expect("a category file")).unwrap();
+    assert_eq!(category["link"]["id"], "tricky_types/lib/index");
+}
+
+#[test]
+fn module_sidebars_leave_the_site_sidebars_file_alone() {
+    let mut config = Config::default();
+    config.output.sidebar_mode = SidebarMode::Module;
+    let files = render(config);
+
+    assert!(file(&files, "sidebars.js").is_none(), "the site's sidebars.js is overwritten");
+    let module = file(&files, "sidebars-api.js").expect("the sidebar module");
+    assert!(module.starts_with("module.exports = ["), "{}", module);
+
+    let mut config = Config::default();
+    config.output.sidebar_mode = SidebarMode::Module;
+    config.output.sidebar_file = Some("api/sidebar.js".into());
+    assert!(file(&render(config), "api/sidebar.js").is_some());
+}
+
+#[test]
+fn item_links_are_served_under_the_docs_route() {
+    let files = render(Config::default());
+    let sidebars = file(&files, "sidebars.js").expect("a sidebars file");
+    assert!(sidebars.contains("\"href\": \"/docs/tricky_types/lib#struct-Storage\""), "{}", sidebars);
+
+    let mut config = Config::default();
+    config.site.base_path = Some("/reference/".to_string());
+    let files = render(config);
+    let sidebars = file(&files, "sidebars.js").expect("a sidebars file");
+    assert!(sidebars.contains("\"href\": \"/reference/tricky_types/lib#struct-Storage\""), "{}", sidebars);
+}
+
+#[test]
+fn modules_nest_under_their_parent_module() {
+    let category = module_tree_category(Config::default());
+    assert_eq!(category["label"], "module_tree");
+    assert_eq!(category["link"]["id"], "module_tree/index");
+    assert_eq!(labels(&category), ["lib", "utils"]);
+
+    let utils = &category["items"][1];
+    assert_eq!(utils["link"]["id"], "module_tree/utils");
+    assert_eq!(labels(utils), ["mod2", "one"]);
+    assert_eq!(utils["items"][0]["type"], "category");
+    assert_eq!(utils["items"][0]["link"]["id"], "module_tree/utils/mod2");
+    assert_eq!(labels(&utils["items"][0]), ["two"]);
+}
+
+#[test]
+fn categories_collapse_from_the_configured_depth() {
+    // Whether the crate, `utils` and `utils::mod2` start collapsed
+    let collapsed = |config: Config| {
+        let category = module_tree_category(config);
+        let utils = &category["items"][1];
+        [&category, utils, &utils["items"][0]].map(|category| category["collapsed"].as_bool().unwrap())
+    };
+
+    assert_eq!(collapsed(Config::default()), [false, true, true]);
+
+    let mut config = Config::default();
+    config.output.sidebar_collapsed_depth = 2;
+    assert_eq!(collapsed(config), [false, false, true]);
+
+    let mut config = Config::default();
+    config.output.sidebar_collapsed_depth = 0;
+    assert_eq!(collapsed(config), [true, true, true]);
+
+    let mut config = Config::default();
+    config.output.sidebar_collapsible = false;
+    assert_eq!(collapsed(config), [false, false, false], "categories that cannot collapse start collapsed");
+    let mut config = Config::default();
+    config.output.sidebar_collapsible = false;
+    assert_eq!(module_tree_category(config)["items"][1]["collapsible"], false);
+}
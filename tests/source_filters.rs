@@ -0,0 +1,43 @@
+use noir_docs_generator::{DocBuilder, Error};
+
+mod common;
+
+use common::WORKSPACE;
+
+// The module paths of the `aztec` crate parsed with `include` and `exclude`
+fn modules(include: &[&str], exclude: &[&str]) -> Vec<String> {
+    let mut config = common::config();
+    config.source.include = include.iter().map(|glob| glob.to_string()).collect();
+    config.source.exclude = exclude.iter().map(|glob| glob.to_string()).collect();
+    let (libraries, errors) = DocBuilder::new(config).input(WORKSPACE).parse().expect("the workspace parses");
+    assert!(errors.is_empty(), "{:?}", errors);
+    let library = libraries.iter().find(|library| library.name == "aztec").expect("the nested crate");
+    let mut module_paths: Vec<String> = library.files.iter().map(|file| file.module_path.join("::")).collect();
+    module_paths.sort();
+    module_paths
+}
+
+#[test]
+fn skip_marker_leaves_a_file_out() {
+    assert_eq!(modules(&["**/*.nr"], &[]), ["", "tests::t"]);
+}
+
+#[test]
+fn exclude_globs_drop_matching_files() {
+    assert_eq!(modules(&["**/*.nr"], &["**/tests/**"]), [""]);
+}
+
+#[test]
+fn include_globs_select_matching_files() {
+    assert_eq!(modules(&["**/tests/*.nr"], &[]), ["tests::t"]);
+}
+
+#[test]
+fn invalid_globs_name_their_setting() {
+    let mut config = common::config();
+    config.source.exclude = vec!["**/target/**".to_string(), "src/[lib.nr".to_string()];
+    match DocBuilder::new(config).input(WORKSPACE).parse() {
+        Err(Error::Config { key, .. }) => assert_eq!(key.as_deref(), Some("source.exclude[1]")),
+        other => panic!("expected a config error, got {:?}", other.map(|(libraries, _)| libraries.len())),
+    }
+}
@@ -0,0 +1,74 @@
+use noir_docs_generator::config::PageMode;
+
+mod common;
+
+use common::{page, WORKSPACE};
+
+fn render(page_mode: PageMode) -> Vec<(String, String)> {
+    let mut config = common::config();
+    config.output.page_mode = page_mode;
+    common::render(WORKSPACE, config)
+}
+
+// The lines of the "Used by:" list following `heading`
+fn used_by<'a>(content: &'a str, heading: &str) -> Vec<&'a str> {
+    let section = &content[content.find(heading).unwrap_or_else(|| panic!("no {} in {}", heading, content))..];
+    let list = &section[section.find("Used by:\n").expect("a Used by list") + "Used by:\n".len()..];
+    list.lines().take_while(|line| line.starts_with("- ")).collect()
+}
+
+#[test]
+fn lists_public_users_of_a_struct() {
+    let files = render(PageMode::Module);
+    let uses = used_by(page(&files, "docs/aztec/lib.md"), "### Note {#struct-Note}");
+    assert!(uses.contains(&"- [`aztec::tests::t::check`](../aztec/tests/t.md#fn-check) (function)"), "{:#?}", uses);
+    assert!(uses.contains(&"- [`aztec::tests::t::Case::note`](../aztec/tests/t.md#struct-Case) (field)"), "{:#?}", uses);
+    // `Wrapper::secret` is private
+    assert!(!uses.iter().any(|line| line.contains("Wrapper")), "{:#?}", uses);
+}
+
+#[test]
+fn follows_type_aliases() {
+    let files = render(PageMode::Module);
+    let uses = used_by(page(&files, "docs/aztec/lib.md"), "### Note {#struct-Note}");
+    assert!(uses.contains(&"- [`aztec::tests::t::TestNote`](../aztec/tests/t.md#type-TestNote) (type alias)"), "{:#?}", uses);
+    assert!(uses.contains(&"- [`aztec::tests::t::make`](../aztec/tests/t.md#fn-make) (function)"), "{:#?}", uses);
+
+    let content = page(&files, "docs/aztec/tests/t.md");
+    assert!(content.contains("## Type Aliases\n"), "{}", content);
+    assert!(content.contains("```rust\ntype TestNote = Note\n```"), "{}", content);
+    assert_eq!(used_by(content, "### TestNote {#type-TestNote}"), ["- [`aztec::tests::t::make`](../../aztec/tests/t.md#fn-make) (function)"]);
+}
+
+#[test]
+fn type_aliases_get_their_own_pages() {
+    let files = render(PageMode::Item);
+    let content = page(&files, "docs/aztec/tests/t/type-TestNote.md");
+    assert!(content.contains("# Type Alias TestNote\n"), "{}", content);
+    assert!(content.contains("Used by:\n- [`aztec::tests::t::make`](../../../aztec/tests/t/fn-make.md) (function)\n"), "{}", content);
+}
+
+#[test]
+fn lists_methods_of_impls_in_other_modules() {
+    let files = render(PageMode::Module);
+    let uses = used_by(page(&files, "docs/aztec/lib.md"), "### Note {#struct-Note}");
+    assert!(uses.contains(&"- [`aztec::Note::same_owner`](../aztec/lib.md#method-Note-same_owner) (method)"), "{:#?}", uses);
+    // `is_owned_by` only takes the note as `self`
+    assert!(!uses.iter().any(|line| line.contains("is_owned_by")), "{:#?}", uses);
+}
+
+#[test]
+fn struct_pages_list_their_users() {
+    let files = render(PageMode::Item);
+    let uses = used_by(page(&files, "docs/aztec/lib/struct-Note.md"), "# Struct Note");
+    assert_eq!(
+        uses,
+        [
+            "- [`aztec::tests::t::Case::note`](../../aztec/tests/t/struct-Case.md) (field)",
+            "- [`aztec::tests::t::check`](../../aztec/tests/t/fn-check.md) (function)",
+            "- [`aztec::tests::t::make`](../../aztec/tests/t/fn-make.md) (function)",
+            "- [`aztec::tests::t::TestNote`](../../aztec/tests/t/type-TestNote.md) (type alias)",
+            "- [`aztec::Note::same_owner`](../../aztec/lib/struct-Note.md#method-Note-same_owner) (method)",
+        ]
+    );
+}